@@ -53,6 +53,7 @@ fn main() {
         id: "1".to_owned(),
     }));
     assert_eq!(StatusCode::FORBIDDEN, error.status());
+    assert_eq!(Some("forbidden"), error.code());
     assert_eq!(
         json!({
             "code": "forbidden",
@@ -65,6 +66,7 @@ fn main() {
         id: "1".to_owned(),
     }));
     assert_eq!(StatusCode::NOT_FOUND, error.status());
+    assert_eq!(Some("notFound"), error.code());
     assert_eq!(
         json!({
             "code": "notFound",
@@ -75,6 +77,7 @@ fn main() {
 
     let error = UpdateUserError::Validation;
     assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, error.status());
+    assert_eq!(Some("validation"), error.code());
     assert_eq!(
         json!({
             "code": "validation",
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use axum::{body::to_bytes, http::StatusCode, response::IntoResponse};
+use breach::HttpError;
+use serde::Serialize;
+use serde_json::json;
+use utoipa::{IntoResponses, ToSchema};
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct ValidationPayload {
+    field: String,
+    message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct NotFoundPayload {
+    id: String,
+}
+
+#[derive(HttpError, Serialize)]
+#[http(utoipa)]
+#[serde(tag = "code", rename_all = "camelCase")]
+enum GetUserByIdError {
+    #[http(status = NOT_FOUND)]
+    NotFound(NotFoundPayload),
+}
+
+#[derive(HttpError, Serialize)]
+#[http(utoipa)]
+#[serde(tag = "code", rename_all = "camelCase")]
+enum RouteError {
+    GetUserById(Box<GetUserByIdError>),
+
+    #[http(status = UNPROCESSABLE_ENTITY)]
+    Validation(Box<ValidationPayload>),
+
+    #[http(status = SERVICE_UNAVAILABLE)]
+    Unavailable,
+}
+
+/// Error of all routes, boxed to keep results small.
+#[derive(HttpError, Serialize)]
+#[http(transparent, axum, utoipa)]
+#[serde(transparent)]
+struct ApiError(Box<RouteError>);
+
+fn status(error: impl HttpError) -> StatusCode {
+    error.status()
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let error = ApiError(Box::new(RouteError::GetUserById(Box::new(
+        GetUserByIdError::NotFound(NotFoundPayload { id: "1".to_owned() }),
+    ))));
+    assert_eq!(StatusCode::NOT_FOUND, error.status());
+    assert_eq!(StatusCode::NOT_FOUND, status(&error));
+    assert_eq!(
+        vec![
+            "ApiError",
+            "RouteError",
+            "GetUserById",
+            "GetUserByIdError",
+            "NotFound"
+        ],
+        error.path()
+    );
+
+    let response = error.into_response();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    assert_eq!(
+        json!({ "code": "notFound", "id": "1" }),
+        serde_json::from_slice::<serde_json::Value>(
+            &to_bytes(response.into_body(), usize::MAX).await?
+        )?
+    );
+
+    let error = Arc::new(RouteError::Validation(Box::new(ValidationPayload {
+        field: "email".to_owned(),
+        message: "invalid email".to_owned(),
+    })));
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, error.status());
+    assert!(Arc::new(RouteError::Unavailable).is_retryable());
+
+    let responses = serde_json::to_value(ApiError::responses())?;
+    assert_eq!(serde_json::to_value(RouteError::responses())?, responses);
+    assert!(responses["404"].is_object());
+    assert!(responses["422"].is_object());
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use breach::{HttpError, http::StatusCode};
+use serde::Serialize;
+use serde_json::json;
+use utoipa::IntoResponses;
+
+#[derive(HttpError, Serialize)]
+#[http(axum, mirror, utoipa)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum CreateOrderError {
+    #[http(status = CONFLICT)]
+    Duplicate,
+
+    #[cfg(feature = "billing")]
+    #[http(status = PAYMENT_REQUIRED)]
+    PaymentRequired,
+}
+
+fn main() -> anyhow::Result<()> {
+    assert_eq!(StatusCode::CONFLICT, CreateOrderError::Duplicate.status());
+
+    let responses = serde_json::to_value(CreateOrderError::responses())?;
+    let codes = responses
+        .as_object()
+        .expect("responses")
+        .keys()
+        .collect::<Vec<_>>();
+
+    let mirror: CreateOrderErrorResponse = serde_json::from_value(json!({ "code": "duplicate" }))?;
+    assert_eq!(StatusCode::CONFLICT, mirror.status());
+
+    #[cfg(feature = "billing")]
+    {
+        assert_eq!(
+            StatusCode::PAYMENT_REQUIRED,
+            CreateOrderError::PaymentRequired.status()
+        );
+        assert_eq!(vec!["402", "409"], codes);
+
+        let mirror: CreateOrderErrorResponse =
+            serde_json::from_value(json!({ "code": "paymentRequired" }))?;
+        assert_eq!(StatusCode::PAYMENT_REQUIRED, mirror.status());
+    }
+
+    #[cfg(not(feature = "billing"))]
+    {
+        assert_eq!(vec!["409"], codes);
+        assert!(
+            serde_json::from_value::<CreateOrderErrorResponse>(
+                json!({ "code": "paymentRequired" })
+            )
+            .is_err()
+        );
+    }
+
+    Ok(())
+}
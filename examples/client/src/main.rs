@@ -0,0 +1,395 @@
+mod server {
+    use std::time::Duration;
+
+    use breach::HttpError;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, HttpError, Serialize)]
+    #[http(status = NOT_FOUND, mirror)]
+    #[serde(rename_all = "camelCase")]
+    pub struct NotFoundError {
+        pub id: String,
+    }
+
+    #[derive(Debug, Deserialize, Serialize)]
+    #[serde(
+        tag = "code",
+        rename_all = "camelCase",
+        rename_all_fields = "camelCase"
+    )]
+    pub enum ValidationError {
+        NameTooShort { min_length: usize },
+    }
+
+    #[derive(HttpError, Serialize)]
+    #[http(mirror)]
+    #[serde(
+        tag = "code",
+        rename_all = "camelCase",
+        rename_all_fields = "camelCase"
+    )]
+    pub enum GetUserByIdError {
+        NotFound(NotFoundError),
+
+        #[http(status = INTERNAL_SERVER_ERROR)]
+        Internal(#[serde(skip)] anyhow::Error),
+    }
+
+    #[derive(HttpError, Serialize)]
+    #[http(mirror)]
+    #[serde(
+        tag = "code",
+        rename_all = "camelCase",
+        rename_all_fields = "camelCase"
+    )]
+    pub enum UpdateUserError {
+        GetUserById(GetUserByIdError),
+
+        #[http(status = UNPROCESSABLE_ENTITY)]
+        Validation,
+    }
+
+    #[derive(HttpError, Serialize)]
+    #[http(mirror)]
+    #[serde(
+        tag = "code",
+        rename_all = "camelCase",
+        rename_all_fields = "camelCase"
+    )]
+    pub enum CreateUserError {
+        #[http(status = UNPROCESSABLE_ENTITY)]
+        Validation(ValidationError),
+
+        #[http(status = CONFLICT)]
+        Conflict(NotFoundError),
+    }
+
+    #[derive(HttpError, Serialize)]
+    #[http(axum, mirror, retry_after = list_users_retry_after)]
+    #[serde(tag = "code", rename_all = "camelCase")]
+    pub enum ListUsersError {
+        #[http(status = TOO_MANY_REQUESTS)]
+        RateLimited,
+
+        #[http(status = CONFLICT, retryable)]
+        Locked,
+
+        #[http(status = FORBIDDEN)]
+        Forbidden,
+    }
+
+    fn list_users_retry_after(error: &ListUsersError) -> Option<Duration> {
+        match error {
+            ListUsersError::RateLimited => Some(Duration::ZERO),
+            _ => None,
+        }
+    }
+}
+
+mod client {
+    use breach::{HttpError, client::Opaque};
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, HttpError, PartialEq)]
+    #[http(status = NOT_FOUND)]
+    #[serde(rename_all = "camelCase")]
+    pub struct NotFoundError {
+        pub id: String,
+    }
+
+    #[derive(Debug, Deserialize, HttpError, PartialEq)]
+    #[serde(
+        tag = "code",
+        rename_all = "camelCase",
+        rename_all_fields = "camelCase"
+    )]
+    pub enum GetUserByIdError {
+        NotFound(NotFoundError),
+
+        #[http(status = INTERNAL_SERVER_ERROR, expose)]
+        Internal(Opaque),
+    }
+
+    #[derive(Debug, Deserialize, HttpError, PartialEq)]
+    #[serde(
+        tag = "code",
+        rename_all = "camelCase",
+        rename_all_fields = "camelCase"
+    )]
+    pub enum UpdateUserError {
+        #[http(status = UNPROCESSABLE_ENTITY)]
+        Validation,
+
+        #[serde(untagged)]
+        GetUserById(GetUserByIdError),
+    }
+}
+
+use std::{
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant, SystemTime},
+};
+
+use anyhow::anyhow;
+use axum::{Router, extract::State, response::IntoResponse, routing::get};
+use breach::{
+    HttpError,
+    client::{ClientError, Opaque, RetryMiddleware, decode, decode_reqwest},
+    http::{Response, StatusCode, header::RETRY_AFTER},
+};
+use bytes::Bytes;
+use reqwest_middleware::ClientBuilder;
+use serde::Serialize;
+use tokio::net::TcpListener;
+
+fn response<E: HttpError + Serialize>(error: E) -> Response<Bytes> {
+    Response::builder()
+        .status(error.status())
+        .body(Bytes::from(
+            serde_json::to_vec(&error).expect("serialized error"),
+        ))
+        .expect("valid response")
+}
+
+async fn list_users(
+    State(attempts): State<Arc<AtomicUsize>>,
+) -> Result<(), server::ListUsersError> {
+    match attempts.fetch_add(1, Ordering::SeqCst) {
+        0 => Err(server::ListUsersError::RateLimited),
+        1 => Err(server::ListUsersError::Locked),
+        _ => Ok(()),
+    }
+}
+
+async fn list_users_forbidden(
+    State(attempts): State<Arc<AtomicUsize>>,
+) -> Result<(), server::ListUsersError> {
+    attempts.fetch_add(1, Ordering::SeqCst);
+
+    Err(server::ListUsersError::Forbidden)
+}
+
+async fn export_users(State(attempts): State<Arc<AtomicUsize>>) -> impl IntoResponse {
+    match attempts.fetch_add(1, Ordering::SeqCst) {
+        // Retry after an HTTP date instead of seconds.
+        0 => Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(
+                RETRY_AFTER,
+                httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(2)),
+            )],
+        )),
+        _ => Ok(()),
+    }
+}
+
+async fn retry() -> anyhow::Result<()> {
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let router = Router::new()
+        .route("/users", get(list_users))
+        .route("/users/forbidden", get(list_users_forbidden))
+        .route("/users/export", get(export_users))
+        .with_state(attempts.clone());
+
+    let listener =
+        TcpListener::bind(SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 0))).await?;
+    let url = format!("http://{}", listener.local_addr()?);
+    tokio::spawn(async move { axum::serve(listener, router).await });
+
+    let response = reqwest::get(format!("{url}/users")).await?;
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+    assert_eq!(
+        Some("0"),
+        response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+    );
+    attempts.store(0, Ordering::SeqCst);
+
+    let client = ClientBuilder::new(reqwest::Client::new())
+        .with(
+            RetryMiddleware::<server::ListUsersErrorResponse>::new()
+                .backoff(Duration::from_millis(1)),
+        )
+        .build();
+
+    let response = client.get(format!("{url}/users")).send().await?;
+    assert!(
+        decode_reqwest::<(), server::ListUsersErrorResponse>(response)
+            .await
+            .is_ok()
+    );
+    assert_eq!(3, attempts.swap(0, Ordering::SeqCst));
+
+    let response = client.get(format!("{url}/users/forbidden")).send().await?;
+    assert!(matches!(
+        decode_reqwest::<(), server::ListUsersErrorResponse>(response).await,
+        Err(ClientError::Http(server::ListUsersErrorResponse::Forbidden))
+    ));
+    assert_eq!(1, attempts.swap(0, Ordering::SeqCst));
+
+    let start = Instant::now();
+    let response = client.get(format!("{url}/users/export")).send().await?;
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(2, attempts.load(Ordering::SeqCst));
+    assert!(start.elapsed() >= Duration::from_millis(500));
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let result =
+        decode::<(), client::UpdateUserError>(response(server::UpdateUserError::GetUserById(
+            server::GetUserByIdError::NotFound(server::NotFoundError { id: "1".to_owned() }),
+        )));
+    assert!(matches!(
+        result,
+        Err(ClientError::Http(client::UpdateUserError::GetUserById(
+            client::GetUserByIdError::NotFound(client::NotFoundError { id })
+        ))) if id == "1"
+    ));
+
+    let result =
+        decode::<(), client::UpdateUserError>(response(server::UpdateUserError::GetUserById(
+            server::GetUserByIdError::Internal(anyhow!("database error")),
+        )));
+    assert!(matches!(
+        result,
+        Err(ClientError::Http(client::UpdateUserError::GetUserById(
+            client::GetUserByIdError::Internal(Opaque)
+        )))
+    ));
+
+    let result =
+        decode::<(), client::UpdateUserError>(response(server::UpdateUserError::Validation));
+    assert!(matches!(
+        result,
+        Err(ClientError::Http(client::UpdateUserError::Validation))
+    ));
+
+    let result = decode::<(), client::UpdateUserError>(
+        Response::builder()
+            .status(StatusCode::BAD_GATEWAY)
+            .body(Bytes::from_static(b"<html>Bad Gateway</html>"))
+            .expect("valid response"),
+    );
+    assert!(matches!(
+        result,
+        Err(ClientError::Unknown {
+            status: StatusCode::BAD_GATEWAY,
+            ..
+        })
+    ));
+
+    let result = decode::<(), client::UpdateUserError>(
+        Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Bytes::from_static(br#"{"code":"validation"}"#))
+            .expect("valid response"),
+    );
+    assert!(matches!(
+        result,
+        Err(ClientError::Unknown {
+            status: StatusCode::NOT_FOUND,
+            ..
+        })
+    ));
+
+    let result = decode::<serde_json::Value, client::UpdateUserError>(
+        Response::builder()
+            .status(StatusCode::OK)
+            .body(Bytes::from_static(br#"{"id":"1"}"#))
+            .expect("valid response"),
+    );
+    assert_eq!(Some(serde_json::json!({ "id": "1" })), result.ok());
+
+    let result = decode::<(), server::UpdateUserErrorResponse>(response(
+        server::UpdateUserError::GetUserById(server::GetUserByIdError::NotFound(
+            server::NotFoundError { id: "1".to_owned() },
+        )),
+    ));
+    assert!(matches!(
+        result,
+        Err(ClientError::Http(server::UpdateUserErrorResponse::GetUserById(
+            server::GetUserByIdErrorResponse::NotFound(server::NotFoundError { id })
+        ))) if id == "1"
+    ));
+
+    let result = decode::<(), server::UpdateUserErrorResponse>(response(
+        server::UpdateUserError::GetUserById(server::GetUserByIdError::Internal(anyhow!(
+            "database error"
+        ))),
+    ));
+    assert!(matches!(
+        result,
+        Err(ClientError::Http(
+            server::UpdateUserErrorResponse::GetUserById(
+                server::GetUserByIdErrorResponse::Internal
+            )
+        ))
+    ));
+
+    let result = decode::<(), server::UpdateUserErrorResponse>(response(
+        server::UpdateUserError::Validation,
+    ));
+    assert!(matches!(
+        result,
+        Err(ClientError::Http(
+            server::UpdateUserErrorResponse::Validation
+        ))
+    ));
+
+    let result = decode::<(), server::CreateUserErrorResponse>(response(
+        server::CreateUserError::Validation(server::ValidationError::NameTooShort {
+            min_length: 1,
+        }),
+    ));
+    assert!(matches!(
+        result,
+        Err(ClientError::Http(
+            server::CreateUserErrorResponse::Validation(server::ValidationError::NameTooShort {
+                min_length: 1
+            })
+        ))
+    ));
+
+    let result = decode::<(), server::CreateUserErrorResponse>(response(
+        server::CreateUserError::Conflict(server::NotFoundError { id: "1".to_owned() }),
+    ));
+    assert!(matches!(
+        result,
+        Err(ClientError::Http(server::CreateUserErrorResponse::Conflict(
+            server::NotFoundError { id }
+        ))) if id == "1"
+    ));
+
+    let result = decode::<(), client::UpdateUserError>(
+        Response::builder()
+            .status(StatusCode::NO_CONTENT)
+            .body(Bytes::new())
+            .expect("valid response"),
+    );
+    assert!(result.is_ok());
+
+    assert!(server::ListUsersError::RateLimited.is_retryable());
+    assert!(server::ListUsersError::Locked.is_retryable());
+    assert!(!server::ListUsersError::Forbidden.is_retryable());
+    assert!(server::ListUsersErrorResponse::Locked.is_retryable());
+    assert!(!server::UpdateUserErrorResponse::Validation.is_retryable());
+
+    let result = decode::<(), client::UpdateUserError>(
+        Response::builder()
+            .status(StatusCode::SERVICE_UNAVAILABLE)
+            .body(Bytes::new())
+            .expect("valid response"),
+    );
+    assert!(result.is_err_and(|error| error.is_retryable()));
+
+    retry().await
+}
@@ -0,0 +1,171 @@
+use std::time::Duration;
+
+use axum::{body::to_bytes, http::StatusCode, response::IntoResponse};
+use breach::{BoxHttpError, DynHttpError, HttpError};
+use serde::Serialize;
+use serde_json::json;
+
+#[derive(HttpError, Serialize)]
+#[serde(tag = "code", rename_all = "camelCase")]
+enum AuthError {
+    #[http(status = UNAUTHORIZED)]
+    Unauthenticated,
+
+    #[http(status = FORBIDDEN, mask = NotFound)]
+    Forbidden,
+
+    #[http(status = NOT_FOUND)]
+    NotFound,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RateLimitedPayload {
+    limit: u32,
+}
+
+#[derive(HttpError, Serialize)]
+#[http(retry_after = rate_limit_retry_after)]
+#[serde(tag = "code", rename_all = "camelCase")]
+enum RateLimitError {
+    #[http(status = TOO_MANY_REQUESTS)]
+    RateLimited(RateLimitedPayload),
+}
+
+fn rate_limit_retry_after(_error: &RateLimitError) -> Option<Duration> {
+    Some(Duration::from_secs(30))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UnavailablePayload {
+    volume: String,
+}
+
+#[derive(HttpError, Serialize)]
+#[serde(tag = "code", rename_all = "camelCase")]
+enum StorageError {
+    #[http(status = INTERNAL_SERVER_ERROR, expose = internal)]
+    Unavailable(UnavailablePayload),
+}
+
+/// Plugin of a middleware chain, failing with errors of its own type.
+trait Plugin {
+    fn check(&self, request: &str) -> Result<(), BoxHttpError>;
+}
+
+struct Auth;
+
+impl Plugin for Auth {
+    fn check(&self, request: &str) -> Result<(), BoxHttpError> {
+        match request {
+            "anonymous" => Err(AuthError::Unauthenticated)?,
+            "secret" => Err(AuthError::Forbidden)?,
+            _ => Ok(()),
+        }
+    }
+}
+
+struct RateLimit;
+
+impl Plugin for RateLimit {
+    fn check(&self, request: &str) -> Result<(), BoxHttpError> {
+        match request {
+            "flood" => Err(RateLimitError::RateLimited(RateLimitedPayload {
+                limit: 10,
+            }))?,
+            _ => Ok(()),
+        }
+    }
+}
+
+struct Storage;
+
+impl Plugin for Storage {
+    fn check(&self, request: &str) -> Result<(), BoxHttpError> {
+        match request {
+            "write" => Err(StorageError::Unavailable(UnavailablePayload {
+                volume: "data".to_owned(),
+            }))?,
+            _ => Ok(()),
+        }
+    }
+}
+
+fn handle(request: &str) -> Result<(), BoxHttpError> {
+    let plugins: Vec<Box<dyn Plugin>> =
+        vec![Box::new(Auth), Box::new(RateLimit), Box::new(Storage)];
+
+    plugins.iter().try_for_each(|plugin| plugin.check(request))
+}
+
+async fn body(response: axum::response::Response) -> anyhow::Result<serde_json::Value> {
+    Ok(serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX).await?,
+    )?)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    assert!(handle("read").is_ok());
+
+    let errors: Vec<Box<dyn DynHttpError>> = ["anonymous", "flood", "write"]
+        .into_iter()
+        .filter_map(|request| handle(request).err())
+        .map(BoxHttpError::into_inner)
+        .collect();
+    assert_eq!(
+        vec![
+            StatusCode::UNAUTHORIZED,
+            StatusCode::TOO_MANY_REQUESTS,
+            StatusCode::INTERNAL_SERVER_ERROR
+        ],
+        errors
+            .iter()
+            .map(|error| error.status())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        json!([
+            { "code": "unauthenticated" },
+            { "code": "rateLimited", "limit": 10 },
+            { "code": "unavailable", "volume": "data" },
+        ]),
+        serde_json::to_value(&errors)?
+    );
+
+    // Boxed errors nested in another body are serialized according to their exposure.
+    let errors: Vec<BoxHttpError> = ["flood", "write"]
+        .into_iter()
+        .filter_map(|request| handle(request).err())
+        .collect();
+    assert_eq!(
+        json!([
+            { "code": "rateLimited", "limit": 10 },
+            { "code": "unavailable" },
+        ]),
+        serde_json::to_value(&errors)?
+    );
+
+    let error = handle("flood").expect_err("rate limited");
+    assert!(error.is_retryable());
+    assert_eq!(Some(Duration::from_secs(30)), error.retry_after());
+
+    let response = error.into_response();
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+    assert_eq!("30", response.headers()["retry-after"]);
+    assert_eq!(
+        json!({ "code": "rateLimited", "limit": 10 }),
+        body(response).await?
+    );
+
+    let response = handle("secret").expect_err("forbidden").into_response();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    assert_eq!(json!({ "code": "notFound" }), body(response).await?);
+
+    let response = handle("write").expect_err("unavailable").into_response();
+    assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, response.status());
+    assert_eq!(json!({ "code": "unavailable" }), body(response).await?);
+
+    Ok(())
+}
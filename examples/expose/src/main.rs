@@ -0,0 +1,129 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+use anyhow::anyhow;
+use axum::{body::to_bytes, response::IntoResponse};
+use breach::{Environment, HttpError, set_environment};
+use serde::Serialize;
+use serde_json::{Value, json};
+use utoipa::{IntoResponses, ToSchema};
+
+#[derive(HttpError, Serialize, ToSchema)]
+#[http(status = NOT_FOUND, utoipa)]
+#[serde(rename_all = "camelCase")]
+struct NotFoundError {
+    id: String,
+}
+
+#[derive(Debug, Serialize)]
+struct DatabaseError {
+    query: String,
+}
+
+impl Display for DatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to execute query `{}`", self.query)
+    }
+}
+
+impl Error for DatabaseError {}
+
+#[derive(HttpError, Serialize)]
+#[http(axum, utoipa)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum GetUserByIdError {
+    NotFound(NotFoundError),
+
+    #[http(status = INTERNAL_SERVER_ERROR)]
+    Database(#[http(expose = internal)] DatabaseError),
+
+    #[http(status = INTERNAL_SERVER_ERROR, expose = internal)]
+    Internal(#[serde(skip)] anyhow::Error),
+}
+
+// Delegating enums serialize the inner error according to its exposure as well.
+#[derive(HttpError, Serialize)]
+#[http(axum)]
+#[serde(untagged)]
+enum ApiError {
+    GetUserById(GetUserByIdError),
+}
+
+async fn body(error: impl IntoResponse) -> anyhow::Result<Value> {
+    let response = error.into_response();
+
+    Ok(serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX).await?,
+    )?)
+}
+
+fn database() -> GetUserByIdError {
+    GetUserByIdError::Database(DatabaseError {
+        query: "SELECT * FROM users".to_owned(),
+    })
+}
+
+fn internal() -> GetUserByIdError {
+    GetUserByIdError::Internal(anyhow!("connection refused").context("database unavailable"))
+}
+
+fn not_found() -> GetUserByIdError {
+    GetUserByIdError::NotFound(NotFoundError { id: "1".to_owned() })
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    assert_eq!(Environment::Production, breach::environment());
+
+    assert_eq!(
+        json!({ "code": "notFound", "id": "1" }),
+        body(not_found()).await?
+    );
+    assert_eq!(json!({ "code": "database" }), body(database()).await?);
+    assert_eq!(json!({ "code": "internal" }), body(internal()).await?);
+    assert_eq!(
+        json!({ "code": "notFound", "id": "1" }),
+        body(ApiError::GetUserById(not_found())).await?
+    );
+    assert_eq!(json!({}), body(ApiError::GetUserById(database())).await?);
+
+    set_environment(Environment::Development);
+
+    assert_eq!(
+        json!({ "code": "notFound", "id": "1" }),
+        body(not_found()).await?
+    );
+    assert_eq!(
+        json!({
+            "code": "database",
+            "query": "SELECT * FROM users",
+            "detail": "failed to execute query `SELECT * FROM users`",
+            "sources": [],
+        }),
+        body(database()).await?
+    );
+    assert_eq!(
+        json!({
+            "code": "internal",
+            "detail": "database unavailable",
+            "sources": ["connection refused"],
+        }),
+        body(internal()).await?
+    );
+
+    let responses = serde_json::to_value(GetUserByIdError::responses())?;
+    assert_eq!(
+        json!({ "type": "string" }),
+        responses["404"]["content"]["application/json"]["schema"]["properties"]["id"]
+    );
+    assert!(responses["500"].is_object());
+    assert!(!responses["500"].to_string().contains("query"));
+
+    Ok(())
+}
@@ -0,0 +1,4 @@
+user-not-found = Benutzer { $id } existiert nicht.
+    .title = Benutzer nicht gefunden
+rateLimited = Zu viele Anfragen, versuche es in { $seconds } Sekunden erneut.
+    .title = Zu viele Anfragen
@@ -0,0 +1,6 @@
+user-not-found = User { $id } does not exist.
+    .title = User not found
+rateLimited = Too many requests, try again in { $seconds } seconds.
+    .title = Rate limited
+internal = Something went wrong on our side.
+    .title = Internal error
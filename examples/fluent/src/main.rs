@@ -0,0 +1,138 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    extract::Path,
+    http::{Request, StatusCode, header::ACCEPT_LANGUAGE},
+    routing::get,
+};
+use breach::{
+    HttpError,
+    fluent::{LanguageIdentifier, LocalizeLayer, Localizer},
+};
+use serde::Serialize;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+#[derive(HttpError, Serialize)]
+#[http(status = NOT_FOUND, message_key = "user-not-found")]
+#[serde(rename_all = "camelCase")]
+struct NotFoundError {
+    id: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RateLimitedPayload {
+    seconds: u64,
+}
+
+#[derive(HttpError, Serialize)]
+#[http(axum, localize)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum GetUserByIdError {
+    NotFound(NotFoundError),
+
+    #[http(status = TOO_MANY_REQUESTS)]
+    RateLimited(RateLimitedPayload),
+
+    #[http(status = INTERNAL_SERVER_ERROR)]
+    Internal(#[serde(skip)] anyhow::Error),
+}
+
+async fn get_user_by_id(Path(id): Path<String>) -> Result<(), GetUserByIdError> {
+    match id.as_str() {
+        "0" => Err(GetUserByIdError::Internal(anyhow!("database error"))),
+        "flood" => Err(GetUserByIdError::RateLimited(RateLimitedPayload {
+            seconds: 30,
+        })),
+        _ => Err(GetUserByIdError::NotFound(NotFoundError { id })),
+    }
+}
+
+async fn send(
+    router: &Router,
+    uri: &str,
+    accept_language: &str,
+) -> anyhow::Result<(StatusCode, Value)> {
+    let response = router
+        .clone()
+        .oneshot(
+            Request::get(uri)
+                .header(ACCEPT_LANGUAGE, accept_language)
+                .body(Body::empty())?,
+        )
+        .await?;
+    let status = response.status();
+    let body = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await?)?;
+
+    Ok((status, body))
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let en: LanguageIdentifier = "en".parse()?;
+    let de: LanguageIdentifier = "de".parse()?;
+
+    let localizer = Localizer::new(en.clone())
+        .with_resource(en, include_str!("../locales/en.ftl"))?
+        .with_resource(de.clone(), include_str!("../locales/de.ftl"))?;
+
+    // Walk all variants to find messages missing from the catalogs.
+    assert_eq!(
+        vec!["user-not-found", "rateLimited", "internal"],
+        GetUserByIdError::message_keys()
+    );
+    assert_eq!(
+        vec![(&de, "internal")],
+        localizer.missing_keys::<GetUserByIdError>()
+    );
+
+    let router = Router::new()
+        .route("/users/{id}", get(get_user_by_id))
+        .layer(LocalizeLayer::new(Arc::new(localizer)));
+
+    let (status, body) = send(&router, "/users/1", "de-CH, de;q=0.9, en;q=0.8").await?;
+    assert_eq!(StatusCode::NOT_FOUND, status);
+    assert_eq!(
+        json!({
+            "code": "notFound",
+            "id": "1",
+            "title": "Benutzer nicht gefunden",
+            "message": "Benutzer 1 existiert nicht.",
+        }),
+        body
+    );
+
+    let (status, body) = send(&router, "/users/flood", "fr, en").await?;
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, status);
+    assert_eq!(
+        json!({
+            "code": "rateLimited",
+            "seconds": 30,
+            "title": "Rate limited",
+            "message": "Too many requests, try again in 30 seconds.",
+        }),
+        body
+    );
+
+    // Missing messages fall back to the default locale.
+    let (status, body) = send(&router, "/users/0", "de").await?;
+    assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, status);
+    assert_eq!(
+        json!({
+            "code": "internal",
+            "title": "Internal error",
+            "message": "Something went wrong on our side.",
+        }),
+        body
+    );
+
+    Ok(())
+}
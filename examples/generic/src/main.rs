@@ -0,0 +1,100 @@
+use axum::{body::to_bytes, http::StatusCode, response::IntoResponse};
+use breach::HttpError;
+use serde::Serialize;
+use serde_json::{Value, json};
+use utoipa::{IntoResponses, ToSchema};
+
+#[derive(HttpError, Serialize, ToSchema)]
+#[http(status = NOT_FOUND, utoipa)]
+#[serde(rename_all = "camelCase")]
+struct NotFoundError {
+    id: String,
+}
+
+#[derive(HttpError, Serialize)]
+#[http(axum, utoipa)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum GetUserByIdError {
+    NotFound(NotFoundError),
+}
+
+/// Wrapper adding authentication errors to any HTTP error.
+#[derive(HttpError, Serialize)]
+#[http(axum, utoipa)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum Authenticated<E> {
+    #[http(status = UNAUTHORIZED)]
+    Unauthorized,
+
+    #[serde(untagged)]
+    Inner(E),
+}
+
+/// Wrapper adding pagination errors, with explicit bounds.
+#[derive(HttpError, Serialize)]
+#[http(axum, bound = "E: HttpError")]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum Paginated<E> {
+    #[http(status = BAD_REQUEST)]
+    InvalidCursor,
+
+    #[serde(untagged)]
+    Inner(E),
+}
+
+async fn send(error: impl IntoResponse) -> anyhow::Result<(StatusCode, Value)> {
+    let response = error.into_response();
+    let status = response.status();
+    let body = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await?)?;
+
+    Ok((status, body))
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let (status, body) = send(Authenticated::<GetUserByIdError>::Unauthorized).await?;
+    assert_eq!(StatusCode::UNAUTHORIZED, status);
+    assert_eq!(json!({ "code": "unauthorized" }), body);
+
+    let (status, body) = send(Authenticated::Inner(GetUserByIdError::NotFound(
+        NotFoundError { id: "1".to_owned() },
+    )))
+    .await?;
+    assert_eq!(StatusCode::NOT_FOUND, status);
+    assert_eq!(json!({ "code": "notFound", "id": "1" }), body);
+
+    let responses = serde_json::to_value(Authenticated::<GetUserByIdError>::responses())?;
+    assert_eq!(
+        vec!["401", "404"],
+        responses
+            .as_object()
+            .expect("responses")
+            .keys()
+            .collect::<Vec<_>>()
+    );
+
+    let error = Paginated::Inner(Authenticated::<GetUserByIdError>::Unauthorized);
+    assert_eq!(StatusCode::UNAUTHORIZED, error.status());
+
+    let (status, body) = send(error).await?;
+    assert_eq!(StatusCode::UNAUTHORIZED, status);
+    assert_eq!(json!({ "code": "unauthorized" }), body);
+    assert_eq!(
+        StatusCode::BAD_REQUEST,
+        Paginated::<GetUserByIdError>::InvalidCursor.status()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use anyhow::anyhow;
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    extract::Path,
+    http::{
+        Request, StatusCode,
+        header::{ACCEPT, CONTENT_TYPE},
+    },
+    routing::get,
+};
+use breach::{
+    HttpError,
+    negotiate::{Encoders, HtmlEncoder, NegotiateLayer},
+};
+use serde::Serialize;
+use tower::ServiceExt;
+use utoipa::{IntoResponses, ToSchema};
+
+#[derive(Debug, HttpError, Serialize, ToSchema)]
+#[http(status = NOT_FOUND, utoipa, message = "user {id} does not exist")]
+#[serde(rename_all = "camelCase")]
+struct NotFoundError {
+    id: String,
+}
+
+#[derive(Debug, HttpError, Serialize, ToSchema)]
+#[http(status = FORBIDDEN, utoipa, message = "you may not view user {id}")]
+#[serde(rename_all = "camelCase")]
+struct ForbiddenError {
+    id: String,
+}
+
+#[derive(Debug, HttpError, Serialize)]
+#[http(axum, negotiate, utoipa)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum GetUserByIdError {
+    NotFound(NotFoundError),
+
+    Forbidden(ForbiddenError),
+
+    #[http(status = INTERNAL_SERVER_ERROR, expose = internal, message = "database error: {0}")]
+    Internal(#[serde(skip)] anyhow::Error),
+}
+
+async fn get_user_by_id(Path(id): Path<String>) -> Result<(), GetUserByIdError> {
+    match id.as_str() {
+        "0" => Err(GetUserByIdError::Internal(anyhow!("connection refused"))),
+        "admin" => Err(GetUserByIdError::Forbidden(ForbiddenError { id })),
+        _ => Err(GetUserByIdError::NotFound(NotFoundError { id })),
+    }
+}
+
+async fn send(
+    router: &Router,
+    uri: &str,
+    accept: &str,
+) -> anyhow::Result<(StatusCode, String, String)> {
+    let response = router
+        .clone()
+        .oneshot(
+            Request::get(uri)
+                .header(ACCEPT, accept)
+                .body(Body::empty())?,
+        )
+        .await?;
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .map(|value| value.to_str())
+        .transpose()?
+        .unwrap_or_default()
+        .to_owned();
+    let body = String::from_utf8(to_bytes(response.into_body(), usize::MAX).await?.to_vec())?;
+
+    Ok((status, content_type, body))
+}
+
+const BROWSER: &str = "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8";
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let html = HtmlEncoder::new().with_template(
+        "forbidden",
+        r#"<h1>Access denied</h1><p>{{ message }}</p><a href="/login">Log in as another user than {{ error.id }}</a>"#,
+    )?;
+
+    let router = Router::new()
+        .route("/users/{id}", get(get_user_by_id))
+        .layer(NegotiateLayer::new(Encoders::default().with(html)));
+
+    // The default page shows status, reason and message, escaped.
+    let (status, content_type, body) = send(&router, "/users/%3Cb%3E", BROWSER).await?;
+    assert_eq!(StatusCode::NOT_FOUND, status);
+    assert_eq!("text/html; charset=utf-8", content_type);
+    assert!(body.contains("<title>404 Not Found</title>"));
+    assert!(body.contains("<p>user &lt;b&gt; does not exist</p>"));
+
+    // Variants override the page with a template for their code.
+    let (status, _, body) = send(&router, "/users/admin", BROWSER).await?;
+    assert_eq!(StatusCode::FORBIDDEN, status);
+    assert_eq!(
+        r#"<h1>Access denied</h1><p>you may not view user admin</p><a href="/login">Log in as another user than admin</a>"#,
+        body
+    );
+
+    // Internal messages are omitted in production.
+    let (status, _, body) = send(&router, "/users/0", BROWSER).await?;
+    assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, status);
+    assert!(body.contains("<h1>500 Internal Server Error</h1>"));
+    assert!(!body.contains("connection refused"));
+
+    // API clients still receive JSON.
+    let (_, content_type, body) = send(&router, "/users/1", "*/*").await?;
+    assert_eq!("application/json", content_type);
+    assert_eq!(r#"{"code":"notFound","id":"1"}"#, body);
+
+    let responses = serde_json::to_value(GetUserByIdError::responses())?;
+    let content = responses["404"]["content"]
+        .as_object()
+        .map(|content| content.keys().cloned().collect::<Vec<_>>())
+        .unwrap_or_default();
+    assert_eq!(
+        vec![
+            "application/json",
+            "application/problem+json",
+            "text/html; charset=utf-8",
+            "text/plain; charset=utf-8",
+        ],
+        content
+    );
+    assert_eq!(
+        serde_json::json!({ "type": "string" }),
+        responses["404"]["content"]["text/html; charset=utf-8"]["schema"]
+    );
+
+    Ok(())
+}
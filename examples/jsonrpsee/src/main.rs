@@ -0,0 +1,117 @@
+use anyhow::anyhow;
+use breach::{
+    HttpError,
+    http::StatusCode,
+    jsonrpsee::{ErrorCodes, error_object, jsonrpsee_types::ErrorObjectOwned},
+};
+use serde::Serialize;
+use serde_json::json;
+
+#[derive(HttpError, Serialize)]
+#[http(status = NOT_FOUND, jsonrpsee)]
+#[serde(rename_all = "camelCase")]
+struct NotFoundError {
+    id: String,
+}
+
+#[derive(Serialize)]
+struct UpstreamError {
+    service: String,
+}
+
+#[derive(HttpError, Serialize)]
+#[http(jsonrpsee)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum GetUserByIdError {
+    NotFound(NotFoundError),
+
+    #[http(status = TOO_MANY_REQUESTS, jsonrpc = -32029)]
+    RateLimited,
+
+    // Only the serialized name is the error code.
+    #[http(status = SERVICE_UNAVAILABLE)]
+    #[serde(rename(deserialize = "maintenanceMode"))]
+    Maintenance,
+
+    // The upstream service is only sent in development.
+    #[http(status = BAD_GATEWAY, expose = internal)]
+    Upstream(UpstreamError),
+
+    #[http(status = INTERNAL_SERVER_ERROR)]
+    Internal(#[serde(skip)] anyhow::Error),
+}
+
+#[derive(HttpError, Serialize)]
+#[http(jsonrpsee)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum UpdateUserError {
+    GetUserById(GetUserByIdError),
+
+    #[http(status = UNPROCESSABLE_ENTITY)]
+    Validation,
+}
+
+fn main() {
+    let error = ErrorObjectOwned::from(UpdateUserError::GetUserById(GetUserByIdError::NotFound(
+        NotFoundError { id: "1".to_owned() },
+    )));
+    assert_eq!(-404, error.code());
+    assert_eq!("notFound", error.message());
+    assert_eq!(
+        Some(json!({
+            "code": "notFound",
+            "id": "1",
+        })),
+        error
+            .data()
+            .map(|data| serde_json::from_str(data.get()).expect("valid data"))
+    );
+
+    let error = ErrorObjectOwned::from(UpdateUserError::Validation);
+    assert_eq!(-32602, error.code());
+    assert_eq!("validation", error.message());
+
+    let error = ErrorObjectOwned::from(UpdateUserError::GetUserById(GetUserByIdError::RateLimited));
+    assert_eq!(-32029, error.code());
+    assert_eq!("rateLimited", error.message());
+
+    let error = ErrorObjectOwned::from(GetUserByIdError::Maintenance);
+    assert_eq!(-32003, error.code());
+    assert_eq!("maintenance", error.message());
+
+    let error = ErrorObjectOwned::from(GetUserByIdError::Internal(anyhow!("database error")));
+    assert_eq!(-32603, error.code());
+    assert_eq!("internal", error.message());
+
+    let error = ErrorObjectOwned::from(GetUserByIdError::Upstream(UpstreamError {
+        service: "database".to_owned(),
+    }));
+    assert_eq!(-32002, error.code());
+    assert_eq!("upstream", error.message());
+    assert_eq!(
+        Some(json!({})),
+        error
+            .data()
+            .map(|data| serde_json::from_str(data.get()).expect("valid data"))
+    );
+
+    let codes = ErrorCodes::new()
+        .status(StatusCode::NOT_FOUND, -32004)
+        .server_errors(-31000);
+    let error = NotFoundError { id: "1".to_owned() };
+    assert_eq!(StatusCode::NOT_FOUND, error.status());
+    assert_eq!(-32004, error_object(&error, &codes).code());
+    assert_eq!("Not Found", error_object(&error, &codes).message());
+    assert_eq!(
+        -31003,
+        error_object(&GetUserByIdError::Maintenance, &codes).code()
+    );
+}
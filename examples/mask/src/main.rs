@@ -0,0 +1,90 @@
+use std::sync::atomic::{AtomicU16, Ordering};
+
+use axum::{body::to_bytes, http::StatusCode, response::IntoResponse};
+use breach::HttpError;
+use serde::Serialize;
+use serde_json::{Value, json};
+use utoipa::{IntoResponses, ToSchema};
+
+static LAST_STATUS: AtomicU16 = AtomicU16::new(0);
+
+#[derive(HttpError, Serialize, ToSchema)]
+#[http(status = NOT_FOUND, utoipa)]
+#[serde(rename_all = "camelCase")]
+struct NotFoundError {
+    id: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ForbiddenError {
+    id: String,
+    owner_id: String,
+}
+
+impl From<&ForbiddenError> for NotFoundError {
+    fn from(value: &ForbiddenError) -> Self {
+        NotFoundError {
+            id: value.id.clone(),
+        }
+    }
+}
+
+fn record_status(error: &GetDocumentError) {
+    LAST_STATUS.store(error.status().as_u16(), Ordering::Relaxed);
+}
+
+#[derive(HttpError, Serialize)]
+#[http(axum, hook = record_status, utoipa)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum GetDocumentError {
+    NotFound(NotFoundError),
+
+    /// Sent as `NotFound` to hide the existence of the document.
+    #[http(status = FORBIDDEN, mask = NotFound)]
+    Forbidden(ForbiddenError),
+}
+
+async fn send(error: GetDocumentError) -> anyhow::Result<(StatusCode, Value)> {
+    let response = error.into_response();
+    let status = response.status();
+    let body = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await?)?;
+
+    Ok((status, body))
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let (status, body) = send(GetDocumentError::Forbidden(ForbiddenError {
+        id: "1".to_owned(),
+        owner_id: "2".to_owned(),
+    }))
+    .await?;
+    assert_eq!(StatusCode::NOT_FOUND, status);
+    assert_eq!(json!({ "code": "notFound", "id": "1" }), body);
+    assert_eq!(403, LAST_STATUS.load(Ordering::Relaxed));
+
+    let (status, body) = send(GetDocumentError::NotFound(NotFoundError {
+        id: "3".to_owned(),
+    }))
+    .await?;
+    assert_eq!(StatusCode::NOT_FOUND, status);
+    assert_eq!(json!({ "code": "notFound", "id": "3" }), body);
+    assert_eq!(404, LAST_STATUS.load(Ordering::Relaxed));
+
+    let responses = serde_json::to_value(GetDocumentError::responses())?;
+    assert_eq!(
+        vec!["404"],
+        responses
+            .as_object()
+            .expect("responses")
+            .keys()
+            .collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use std::error::Error;
+
+use anyhow::anyhow;
+use axum::{body::to_bytes, response::IntoResponse};
+use breach::{Environment, HttpError, set_environment};
+use serde::Serialize;
+use serde_json::{Value, json};
+
+#[derive(Debug, HttpError, Serialize)]
+#[http(status = NOT_FOUND, message = "user {id} not found")]
+#[serde(rename_all = "camelCase")]
+struct UserNotFoundError {
+    id: String,
+}
+
+#[derive(Debug, HttpError, Serialize)]
+#[http(status = TOO_MANY_REQUESTS, message = "rate limited, retry in {0} seconds")]
+struct RateLimitedError(
+    u64,
+    #[serde(skip)]
+    #[http(source)]
+    std::io::Error,
+);
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConflictPayload {
+    email: String,
+}
+
+#[derive(Debug, HttpError, Serialize)]
+#[http(axum, message = "failed to create user", message_field = "detail")]
+#[serde(tag = "code", rename_all = "camelCase")]
+enum CreateUserError {
+    #[http(status = CONFLICT, message = "user with email {email} already exists")]
+    Conflict(ConflictPayload),
+
+    #[http(status = UNPROCESSABLE_ENTITY)]
+    Validation,
+
+    #[http(status = INTERNAL_SERVER_ERROR, expose = internal, message = "internal error: {0}")]
+    Internal(#[serde(skip)] anyhow::Error),
+}
+
+/// Delegating variants delegate to the message of the inner error.
+#[derive(Debug, HttpError, Serialize)]
+#[http(message = "user error")]
+#[serde(untagged)]
+enum UserError {
+    GetById(UserNotFoundError),
+
+    Create(CreateUserError),
+}
+
+async fn body(error: CreateUserError) -> anyhow::Result<Value> {
+    let response = error.into_response();
+
+    Ok(serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX).await?,
+    )?)
+}
+
+fn create_user(email: &str) -> Result<(), CreateUserError> {
+    Err(CreateUserError::Conflict(ConflictPayload {
+        email: email.to_owned(),
+    }))
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let error = UserNotFoundError { id: "1".to_owned() };
+    assert_eq!("user 1 not found", error.to_string());
+    assert!(error.source().is_none());
+
+    let error = RateLimitedError(30, std::io::Error::other("quota exceeded"));
+    assert_eq!("rate limited, retry in 30 seconds", error.to_string());
+    assert_eq!(
+        Some("quota exceeded".to_owned()),
+        error.source().map(ToString::to_string)
+    );
+
+    let error = UserError::GetById(UserNotFoundError { id: "1".to_owned() });
+    assert_eq!("user 1 not found", error.to_string());
+    // The source is forwarded with the message, so the message is not repeated in the error chain.
+    assert!(error.source().is_none());
+
+    let error = anyhow::Error::from(UserError::Create(CreateUserError::Internal(anyhow!(
+        "connection refused"
+    ))));
+    assert_eq!(
+        vec!["internal error: connection refused", "connection refused"],
+        error.chain().map(ToString::to_string).collect::<Vec<_>>()
+    );
+
+    assert_eq!(
+        "failed to create user",
+        CreateUserError::Validation.to_string()
+    );
+
+    // HTTP errors are `std::error::Error`, so they can be used outside of HTTP handlers.
+    let error = anyhow::Error::from(UserError::Create(
+        create_user("user@example.com").expect_err("conflict"),
+    ));
+    assert_eq!(
+        "user with email user@example.com already exists",
+        error.to_string()
+    );
+
+    let error = CreateUserError::Internal(anyhow!("connection refused"));
+    assert_eq!("internal error: connection refused", error.to_string());
+    assert_eq!(
+        Some("connection refused".to_owned()),
+        error.source().map(ToString::to_string)
+    );
+
+    assert_eq!(
+        json!({
+            "code": "conflict",
+            "email": "user@example.com",
+            "detail": "user with email user@example.com already exists",
+        }),
+        body(create_user("user@example.com").expect_err("conflict")).await?
+    );
+
+    // The message of internal errors is only exposed in development.
+    assert_eq!(
+        json!({ "code": "internal" }),
+        body(CreateUserError::Internal(anyhow!("connection refused"))).await?
+    );
+
+    set_environment(Environment::Development);
+    assert_eq!(
+        json!({
+            "code": "internal",
+            "detail": "internal error: connection refused",
+            "sources": [],
+        }),
+        body(CreateUserError::Internal(anyhow!("connection refused"))).await?
+    );
+
+    Ok(())
+}
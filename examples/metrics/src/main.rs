@@ -0,0 +1,120 @@
+use anyhow::anyhow;
+use axum::{Router, body::Body, extract::Path, http::Request, routing::get};
+use breach::{
+    HttpError,
+    http::StatusCode,
+    metrics::{ERROR_DURATION_SECONDS, ERRORS_TOTAL, RequestStartLayer},
+};
+use metrics_util::{
+    MetricKind,
+    debugging::{DebugValue, DebuggingRecorder},
+};
+use serde::Serialize;
+use tower::ServiceExt;
+
+#[derive(HttpError, Serialize)]
+#[http(status = NOT_FOUND)]
+#[serde(rename_all = "camelCase")]
+struct NotFoundError {
+    id: String,
+}
+
+#[derive(HttpError, Serialize)]
+#[http(axum)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum GetUserByIdError {
+    NotFound(NotFoundError),
+
+    #[http(status = INTERNAL_SERVER_ERROR)]
+    Internal(#[serde(skip)] anyhow::Error),
+}
+
+async fn get_user_by_id(Path(id): Path<String>) -> Result<(), GetUserByIdError> {
+    match id.as_str() {
+        "1" => Ok(()),
+        "2" => Err(GetUserByIdError::Internal(anyhow!("database error"))),
+        _ => Err(GetUserByIdError::NotFound(NotFoundError { id })),
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    recorder.install()?;
+
+    let router = Router::new()
+        .route("/users/{id}", get(get_user_by_id))
+        .layer(RequestStartLayer);
+
+    for (id, status) in [
+        ("1", StatusCode::OK),
+        ("2", StatusCode::INTERNAL_SERVER_ERROR),
+        ("3", StatusCode::NOT_FOUND),
+        ("4", StatusCode::NOT_FOUND),
+    ] {
+        let response = router
+            .clone()
+            .oneshot(Request::get(format!("/users/{id}")).body(Body::empty())?)
+            .await?;
+        assert_eq!(status, response.status());
+    }
+
+    let mut counters = Vec::new();
+    let mut histograms = Vec::new();
+    for (key, _, _, value) in snapshotter.snapshot().into_vec() {
+        let (kind, key) = key.into_parts();
+        let labels = key
+            .labels()
+            .map(|label| format!("{}={}", label.key(), label.value()))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        match (kind, value) {
+            (MetricKind::Counter, DebugValue::Counter(value)) => {
+                assert_eq!(ERRORS_TOTAL, key.name());
+                counters.push((labels, value));
+            }
+            (MetricKind::Histogram, DebugValue::Histogram(values)) => {
+                assert_eq!(ERROR_DURATION_SECONDS, key.name());
+                histograms.push((labels, values.len()));
+            }
+            _ => unreachable!("unexpected metric"),
+        }
+    }
+    counters.sort();
+    histograms.sort();
+
+    assert_eq!(
+        vec![
+            (
+                "status=404,code=notFound,type=breach_example_metrics::GetUserByIdError".to_owned(),
+                2
+            ),
+            (
+                "status=500,code=internal,type=breach_example_metrics::GetUserByIdError".to_owned(),
+                1
+            ),
+        ],
+        counters
+    );
+    assert_eq!(
+        vec![
+            (
+                "status=404,code=notFound,type=breach_example_metrics::GetUserByIdError".to_owned(),
+                2
+            ),
+            (
+                "status=500,code=internal,type=breach_example_metrics::GetUserByIdError".to_owned(),
+                1
+            ),
+        ],
+        histograms
+    );
+
+    Ok(())
+}
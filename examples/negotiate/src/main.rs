@@ -0,0 +1,206 @@
+use anyhow::anyhow;
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    extract::Path,
+    http::{
+        Request, StatusCode,
+        header::{ACCEPT, CONTENT_TYPE},
+    },
+    routing::get,
+};
+use breach::{
+    HttpError,
+    negotiate::{Encoders, ErrorBody, ErrorEncoder, NegotiateLayer, ProblemJsonEncoder},
+};
+use serde::Serialize;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+use utoipa::{IntoResponses, ToSchema};
+
+#[derive(Debug, HttpError, Serialize, ToSchema)]
+#[http(status = NOT_FOUND, utoipa, message = "user {id} does not exist")]
+#[serde(rename_all = "camelCase")]
+struct NotFoundError {
+    id: String,
+}
+
+#[derive(Debug, HttpError, Serialize)]
+#[http(axum, negotiate, utoipa, message_field = "detail")]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum GetUserByIdError {
+    NotFound(NotFoundError),
+
+    #[http(status = INTERNAL_SERVER_ERROR, expose = internal, message = "database error: {0}")]
+    Internal(#[serde(skip)] anyhow::Error),
+}
+
+/// Content types of routes which only respond with JSON and problem details.
+const EXPORT_CONTENT_TYPES: &[&str] = &["application/json", "application/problem+json"];
+
+#[derive(Debug, HttpError, Serialize)]
+#[http(negotiate(content_types = EXPORT_CONTENT_TYPES), utoipa)]
+#[serde(tag = "code", rename_all = "camelCase")]
+enum ExportUsersError {
+    #[http(status = 499, reason = "Client Closed Request")]
+    ClientClosed(ExportProgress),
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct ExportProgress {
+    exported: usize,
+}
+
+async fn get_user_by_id(Path(id): Path<String>) -> Result<(), GetUserByIdError> {
+    match id.as_str() {
+        "0" => Err(GetUserByIdError::Internal(anyhow!("connection refused"))),
+        _ => Err(GetUserByIdError::NotFound(NotFoundError { id })),
+    }
+}
+
+async fn send(
+    router: &Router,
+    uri: &str,
+    accept: &str,
+) -> anyhow::Result<(StatusCode, String, Vec<u8>)> {
+    let response = router
+        .clone()
+        .oneshot(
+            Request::get(uri)
+                .header(ACCEPT, accept)
+                .body(Body::empty())?,
+        )
+        .await?;
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .map(|value| value.to_str())
+        .transpose()?
+        .unwrap_or_default()
+        .to_owned();
+    let body = to_bytes(response.into_body(), usize::MAX).await?.to_vec();
+
+    Ok((status, content_type, body))
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let encoders = Encoders::default().with(
+        ProblemJsonEncoder::new()
+            .with_type_base("https://example.com/problems/")
+            .with_instance(true),
+    );
+
+    let router = Router::new()
+        .route("/users/{id}", get(get_user_by_id))
+        .layer(NegotiateLayer::new(encoders));
+
+    let (status, content_type, body) = send(&router, "/users/1", "application/json").await?;
+    assert_eq!(StatusCode::NOT_FOUND, status);
+    assert_eq!("application/json", content_type);
+    assert_eq!(
+        json!({ "code": "notFound", "id": "1", "detail": "user 1 does not exist" }),
+        serde_json::from_slice::<Value>(&body)?
+    );
+
+    // Members of the body don't repeat members of the problem details, e.g. `detail`.
+    let (_, content_type, body) = send(
+        &router,
+        "/users/1",
+        "text/html;q=0.9, application/problem+json",
+    )
+    .await?;
+    assert_eq!("application/problem+json", content_type);
+    assert_eq!(
+        json!({
+            "type": "https://example.com/problems/notFound",
+            "title": "Not Found",
+            "status": 404,
+            "detail": "user 1 does not exist",
+            "instance": "/users/1",
+            "code": "notFound",
+            "id": "1",
+        }),
+        serde_json::from_slice::<Value>(&body)?
+    );
+    assert_eq!(1, String::from_utf8(body)?.matches("\"detail\"").count());
+
+    let (_, content_type, body) = send(&router, "/users/1", "text/*").await?;
+    assert_eq!("text/plain; charset=utf-8", content_type);
+    assert_eq!("user 1 does not exist", String::from_utf8(body)?);
+
+    // Internal messages are omitted in production.
+    let (status, _, body) = send(&router, "/users/0", "text/plain").await?;
+    assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, status);
+    assert_eq!(
+        "500 Internal Server Error (internal)",
+        String::from_utf8(body)?
+    );
+
+    let (_, content_type, body) = send(&router, "/users/1", "application/xml").await?;
+    assert_eq!("application/xml", content_type);
+    assert_eq!(
+        "<error><code>notFound</code><id>1</id><detail>user 1 does not exist</detail></error>",
+        String::from_utf8(body)?
+    );
+
+    let (_, content_type, _) = send(&router, "/users/1", "application/msgpack").await?;
+    assert_eq!("application/msgpack", content_type);
+
+    // Unsupported content types fall back to JSON.
+    let (_, content_type, _) = send(&router, "/users/1", "image/png").await?;
+    assert_eq!("application/json", content_type);
+
+    let responses = serde_json::to_value(GetUserByIdError::responses())?;
+    let content = responses["404"]["content"]
+        .as_object()
+        .map(|content| content.keys().cloned().collect::<Vec<_>>())
+        .unwrap_or_default();
+    assert_eq!(
+        vec![
+            "application/json",
+            "application/msgpack",
+            "application/problem+json",
+            "application/xml",
+            "text/plain; charset=utf-8",
+        ],
+        content
+    );
+    assert_eq!(
+        json!({ "type": "string" }),
+        responses["404"]["content"]["text/plain; charset=utf-8"]["schema"]
+    );
+
+    let responses = serde_json::to_value(ExportUsersError::responses())?;
+    assert_eq!(
+        Some(vec!["application/json", "application/problem+json"]),
+        responses["499"]["content"]
+            .as_object()
+            .map(|content| content.keys().map(String::as_str).collect::<Vec<_>>())
+    );
+
+    // The title of problem details is the reason of the error.
+    let error = ExportUsersError::ClientClosed(ExportProgress { exported: 10 });
+    let body = ErrorBody::new(&error, &error, None);
+    assert_eq!(
+        json!({
+            "type": "about:blank",
+            "title": "Client Closed Request",
+            "status": 499,
+            "code": "clientClosed",
+            "exported": 10,
+        }),
+        serde_json::from_slice::<Value>(
+            &ProblemJsonEncoder::new()
+                .encode(&body, None)
+                .map_err(|error| anyhow!(error))?
+        )?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use std::io;
+
+use anyhow::anyhow;
+use breach::{HttpError, notify};
+use opentelemetry::{
+    KeyValue, Value,
+    trace::{Status, Tracer, TracerProvider},
+};
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider, SpanData};
+use serde::Serialize;
+
+#[derive(HttpError, Serialize)]
+#[http(status = NOT_FOUND)]
+#[serde(rename_all = "camelCase")]
+struct NotFoundError {
+    id: String,
+}
+
+#[derive(HttpError, Serialize)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum GetUserByIdError {
+    NotFound(NotFoundError),
+
+    #[http(status = CONFLICT, level = error)]
+    Corrupted,
+
+    #[http(status = INTERNAL_SERVER_ERROR)]
+    Internal(#[serde(skip)] anyhow::Error),
+}
+
+fn attribute<'a>(attributes: &'a [KeyValue], key: &str) -> Option<&'a Value> {
+    attributes
+        .iter()
+        .find(|attribute| attribute.key.as_str() == key)
+        .map(|attribute| &attribute.value)
+}
+
+fn record(
+    provider: &SdkTracerProvider,
+    exporter: &InMemorySpanExporter,
+    error: GetUserByIdError,
+) -> SpanData {
+    provider
+        .tracer("breach")
+        .in_span("request", |_| notify(&error));
+
+    let mut spans = exporter.get_finished_spans().expect("finished spans");
+    exporter.reset();
+
+    assert_eq!(1, spans.len());
+    spans.remove(0)
+}
+
+fn main() {
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+
+    let span = record(
+        &provider,
+        &exporter,
+        GetUserByIdError::NotFound(NotFoundError { id: "1".to_owned() }),
+    );
+    assert_eq!(Status::Unset, span.status);
+    assert_eq!(
+        Some(&Value::I64(404)),
+        attribute(&span.attributes, "http.response.status_code")
+    );
+    assert_eq!(
+        Some(&Value::from("notFound")),
+        attribute(&span.attributes, "error.type")
+    );
+    assert!(span.events.is_empty());
+
+    let span = record(&provider, &exporter, GetUserByIdError::Corrupted);
+    assert_eq!(Status::error("corrupted"), span.status);
+
+    let span = record(
+        &provider,
+        &exporter,
+        GetUserByIdError::Internal(
+            anyhow!(io::Error::new(
+                io::ErrorKind::ConnectionRefused,
+                "connection refused"
+            ))
+            .context("database error"),
+        ),
+    );
+    assert_eq!(Status::error("internal"), span.status);
+    assert_eq!(
+        Some(&Value::I64(500)),
+        attribute(&span.attributes, "http.response.status_code")
+    );
+
+    let event = span.events.iter().next().expect("exception event");
+    assert_eq!("exception", event.name);
+    assert_eq!(
+        Some(&Value::from("database error: connection refused")),
+        attribute(&event.attributes, "exception.message")
+    );
+}
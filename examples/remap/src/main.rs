@@ -0,0 +1,124 @@
+use anyhow::anyhow;
+use breach::{HttpError, Level, http::StatusCode};
+use serde::Serialize;
+use serde_json::json;
+use utoipa::{IntoResponses, ToSchema};
+
+#[derive(HttpError, Serialize, ToSchema)]
+#[http(status = NOT_FOUND, utoipa)]
+#[serde(rename_all = "camelCase")]
+struct NotFoundError {
+    id: String,
+}
+
+#[derive(HttpError, Serialize)]
+#[http(utoipa)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum GetUserByIdError {
+    NotFound(NotFoundError),
+
+    #[http(status = INTERNAL_SERVER_ERROR)]
+    Internal(#[serde(skip)] anyhow::Error),
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum PostValidationError {
+    TitleTooShort,
+}
+
+#[derive(HttpError, Serialize)]
+#[http(utoipa)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum CreatePostError {
+    #[http(status = UNPROCESSABLE_ENTITY)]
+    Validation(PostValidationError),
+
+    /// The referenced author does not exist.
+    #[http(remap(NOT_FOUND => UNPROCESSABLE_ENTITY))]
+    #[serde(untagged)]
+    Author(GetUserByIdError),
+}
+
+#[derive(HttpError, Serialize)]
+#[http(utoipa)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum ListPostsError {
+    /// The author of a post does not exist, which is a failure of the user service.
+    #[http(remap(NOT_FOUND => BAD_GATEWAY))]
+    #[serde(untagged)]
+    Author(GetUserByIdError),
+}
+
+fn main() -> anyhow::Result<()> {
+    let error = CreatePostError::Validation(PostValidationError::TitleTooShort);
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, error.status());
+
+    let error = CreatePostError::Author(GetUserByIdError::NotFound(NotFoundError {
+        id: "1".to_owned(),
+    }));
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, error.status());
+
+    let error = CreatePostError::Author(GetUserByIdError::Internal(anyhow!("database error")));
+    assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, error.status());
+
+    // Remapped errors behave like errors of the new status.
+    let error = ListPostsError::Author(GetUserByIdError::NotFound(NotFoundError {
+        id: "1".to_owned(),
+    }));
+    assert_eq!(StatusCode::BAD_GATEWAY, error.status());
+    assert_eq!(Level::Error, error.level());
+    assert!(error.is_retryable());
+    assert!(error.is_reported());
+
+    let error = ListPostsError::Author(GetUserByIdError::Internal(anyhow!("database error")));
+    assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, error.status());
+    assert!(!error.is_retryable());
+
+    let responses = serde_json::to_value(CreatePostError::responses())?;
+    assert_eq!(
+        vec!["422", "500"],
+        responses
+            .as_object()
+            .expect("responses")
+            .keys()
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        json!("Unprocessable Entity"),
+        responses["422"]["description"]
+    );
+    assert_eq!(
+        2,
+        responses["422"]["content"]["application/json"]["schema"]["oneOf"]
+            .as_array()
+            .expect("oneOf")
+            .len()
+    );
+
+    // Remapped responses are described by the new status.
+    let responses = serde_json::to_value(ListPostsError::responses())?;
+    assert_eq!(json!("Bad Gateway"), responses["502"]["description"]);
+    assert_eq!(
+        json!("Internal Server Error"),
+        responses["500"]["description"]
+    );
+
+    Ok(())
+}
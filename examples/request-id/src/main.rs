@@ -0,0 +1,154 @@
+use anyhow::anyhow;
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    extract::Path,
+    http::{Request, StatusCode},
+    routing::get,
+};
+use breach::{
+    BoxHttpError, HttpError,
+    request_id::{REQUEST_ID_HEADER, RequestIdLayer},
+};
+use serde::Serialize;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+use utoipa::{IntoResponses, ToSchema};
+
+#[derive(HttpError, Serialize, ToSchema)]
+#[http(status = NOT_FOUND, utoipa)]
+#[serde(rename_all = "camelCase")]
+struct NotFoundError {
+    id: String,
+}
+
+#[derive(HttpError, Serialize)]
+#[http(axum, request_id, utoipa)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum GetUserByIdError {
+    NotFound(NotFoundError),
+
+    #[http(status = INTERNAL_SERVER_ERROR)]
+    Internal(#[serde(skip)] anyhow::Error),
+}
+
+async fn get_user_by_id(Path(id): Path<String>) -> Result<(), GetUserByIdError> {
+    if id == "0" {
+        Err(GetUserByIdError::Internal(anyhow!("database error")))
+    } else {
+        Err(GetUserByIdError::NotFound(NotFoundError { id }))
+    }
+}
+
+async fn get_user_by_id_boxed(Path(id): Path<String>) -> Result<(), BoxHttpError> {
+    let error = BoxHttpError::new(NotFoundError { id: id.clone() });
+
+    // The request ID is only added to boxed errors on request.
+    Err(if id == "0" {
+        error
+    } else {
+        error.with_request_id(true)
+    })
+}
+
+async fn send(
+    router: &Router,
+    request: Request<Body>,
+) -> anyhow::Result<(StatusCode, String, Value)> {
+    let response = router.clone().oneshot(request).await?;
+    let status = response.status();
+
+    let request_id = response
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .expect("request ID header")
+        .to_str()?
+        .to_owned();
+    let body = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await?)?;
+
+    Ok((status, request_id, body))
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let router = Router::new()
+        .route("/users/{id}", get(get_user_by_id))
+        .route("/boxed/users/{id}", get(get_user_by_id_boxed))
+        .layer(RequestIdLayer);
+
+    let (status, request_id, body) = send(
+        &router,
+        Request::get("/users/1")
+            .header(REQUEST_ID_HEADER, "abc")
+            .body(Body::empty())?,
+    )
+    .await?;
+    assert_eq!(StatusCode::NOT_FOUND, status);
+    assert_eq!("abc", request_id);
+    assert_eq!(
+        json!({
+            "code": "notFound",
+            "id": "1",
+            "requestId": "abc",
+        }),
+        body
+    );
+
+    let (status, request_id, body) =
+        send(&router, Request::get("/users/2").body(Body::empty())?).await?;
+    assert_eq!(StatusCode::NOT_FOUND, status);
+    assert_eq!(36, request_id.len());
+    assert_eq!(
+        json!({
+            "code": "notFound",
+            "id": "2",
+            "requestId": request_id,
+        }),
+        body
+    );
+
+    let (status, request_id, body) =
+        send(&router, Request::get("/users/0").body(Body::empty())?).await?;
+    assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, status);
+    assert_eq!(
+        json!({
+            "code": "internal",
+            "requestId": request_id,
+        }),
+        body
+    );
+
+    let (status, request_id, body) =
+        send(&router, Request::get("/boxed/users/1").body(Body::empty())?).await?;
+    assert_eq!(StatusCode::NOT_FOUND, status);
+    assert_eq!(json!({ "id": "1", "requestId": request_id }), body);
+
+    let (status, _, body) =
+        send(&router, Request::get("/boxed/users/0").body(Body::empty())?).await?;
+    assert_eq!(StatusCode::NOT_FOUND, status);
+    assert_eq!(json!({ "id": "0" }), body);
+
+    let responses = serde_json::to_value(GetUserByIdError::responses())?;
+    assert_eq!(
+        json!({
+            "type": "object",
+            "properties": {
+                "requestId": {
+                    "type": "string",
+                    "description": "ID of the request.",
+                },
+            },
+        }),
+        responses["404"]["content"]["application/json"]["schema"]["allOf"][1]
+    );
+    assert_eq!(
+        json!({ "type": "string" }),
+        responses["404"]["content"]["application/json"]["schema"]["allOf"][0]["properties"]["id"]
+    );
+
+    Ok(())
+}
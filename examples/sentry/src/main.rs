@@ -0,0 +1,111 @@
+use std::io;
+
+use anyhow::anyhow;
+use breach::{HttpError, notify};
+use sentry_core::{protocol::Level, test::with_captured_events};
+use serde::Serialize;
+
+#[derive(HttpError, Serialize)]
+#[http(status = NOT_FOUND)]
+#[serde(rename_all = "camelCase")]
+struct NotFoundError {
+    id: String,
+}
+
+#[derive(HttpError, Serialize)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum GetUserByIdError {
+    NotFound(NotFoundError),
+
+    #[http(status = CONFLICT, report)]
+    Corrupted,
+
+    #[http(status = INTERNAL_SERVER_ERROR)]
+    Internal(#[serde(skip)] anyhow::Error),
+}
+
+#[derive(HttpError, Serialize)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum UpdateUserError {
+    GetUserById(GetUserByIdError),
+
+    #[http(status = SERVICE_UNAVAILABLE, report = false)]
+    Maintenance,
+}
+
+fn main() {
+    let error = UpdateUserError::GetUserById(GetUserByIdError::NotFound(NotFoundError {
+        id: "1".to_owned(),
+    }));
+    assert!(!error.is_reported());
+    assert_eq!(
+        vec![
+            "UpdateUserError",
+            "GetUserById",
+            "GetUserByIdError",
+            "NotFound",
+            "NotFoundError"
+        ],
+        error.path()
+    );
+    assert!(with_captured_events(|| notify(&error)).is_empty());
+
+    let error = UpdateUserError::Maintenance;
+    assert!(!error.is_reported());
+    assert!(with_captured_events(|| notify(&error)).is_empty());
+
+    let error = UpdateUserError::GetUserById(GetUserByIdError::Corrupted);
+    assert!(error.is_reported());
+    let events = with_captured_events(|| notify(&error));
+    assert_eq!(1, events.len());
+    assert_eq!(Level::Info, events[0].level);
+    assert_eq!(Some("corrupted"), events[0].message.as_deref());
+    assert_eq!(
+        Some("409"),
+        events[0].tags.get("http.status_code").map(String::as_str)
+    );
+
+    let error = UpdateUserError::GetUserById(GetUserByIdError::Internal(
+        anyhow!(io::Error::new(
+            io::ErrorKind::ConnectionRefused,
+            "connection refused"
+        ))
+        .context("database error"),
+    ));
+    assert!(error.is_reported());
+    let events = with_captured_events(|| notify(&error));
+    assert_eq!(1, events.len());
+
+    let event = &events[0];
+    assert_eq!(Level::Error, event.level);
+    assert_eq!(
+        Some("internal"),
+        event.tags.get("error.code").map(String::as_str)
+    );
+    assert_eq!(
+        [
+            "UpdateUserError",
+            "GetUserById",
+            "GetUserByIdError",
+            "Internal"
+        ]
+        .as_slice(),
+        event.fingerprint.as_ref()
+    );
+    assert_eq!(
+        vec![Some("connection refused"), Some("database error")],
+        event
+            .exception
+            .iter()
+            .map(|exception| exception.value.as_deref())
+            .collect::<Vec<_>>()
+    );
+}
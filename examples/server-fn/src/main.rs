@@ -0,0 +1,68 @@
+use std::convert::Infallible;
+
+use breach::{
+    HttpError,
+    http::{Request, Response, StatusCode},
+    leptos::{
+        ServerFnStatusLayer,
+        server_fn::{
+            Bytes,
+            error::{FromServerFnError, SERVER_FN_ERROR_HEADER, ServerFnErrorErr},
+        },
+    },
+};
+use serde::{Deserialize, Serialize};
+use tower::{Layer, ServiceExt, service_fn};
+
+#[derive(Debug, Deserialize, HttpError, PartialEq, Serialize)]
+#[http(status = NOT_FOUND)]
+#[serde(rename_all = "camelCase")]
+struct NotFoundError {
+    id: String,
+}
+
+#[derive(Debug, Deserialize, HttpError, PartialEq, Serialize)]
+#[http(leptos)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum GetUserError {
+    NotFound(NotFoundError),
+
+    #[http(status = INTERNAL_SERVER_ERROR, expose, server_fn)]
+    ServerFn(ServerFnErrorErr),
+}
+
+#[tokio::main]
+async fn main() {
+    let error = GetUserError::NotFound(NotFoundError { id: "1".to_owned() });
+    let data = error.ser();
+    assert_eq!(GetUserError::de(data), error);
+
+    let error = GetUserError::from_server_fn_error(ServerFnErrorErr::Request(
+        "connection refused".to_owned(),
+    ));
+    assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, error.status());
+    assert_eq!(GetUserError::de(error.ser()), error);
+
+    let service = ServerFnStatusLayer.layer(service_fn(|_: Request<()>| async {
+        let error = GetUserError::NotFound(NotFoundError { id: "1".to_owned() });
+
+        Ok::<_, Infallible>(
+            Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .header(SERVER_FN_ERROR_HEADER, "/api/get_user")
+                .body(error.ser())
+                .expect("valid response"),
+        )
+    }));
+
+    let response: Response<Bytes> = service.oneshot(Request::new(())).await.expect("infallible");
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    assert_eq!(
+        GetUserError::de(response.into_body()),
+        GetUserError::NotFound(NotFoundError { id: "1".to_owned() })
+    );
+}
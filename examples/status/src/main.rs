@@ -0,0 +1,110 @@
+use breach::{HttpError, http::StatusCode};
+use serde::Serialize;
+use serde_json::json;
+use utoipa::{IntoResponses, ToSchema};
+
+mod consts {
+    use breach::http::StatusCode;
+
+    pub const UPSTREAM_TIMEOUT: StatusCode = StatusCode::GATEWAY_TIMEOUT;
+}
+
+/// Error of an upstream service, forwarded with its status.
+#[derive(HttpError, Serialize, ToSchema)]
+#[http(statuses(502, 503, 504), expose, utoipa)]
+#[serde(rename_all = "camelCase")]
+struct UpstreamError {
+    #[http(status)]
+    #[serde(skip)]
+    status: StatusCode,
+    service: String,
+}
+
+#[derive(HttpError, Serialize, ToSchema)]
+#[http(status = self.status, default, utoipa)]
+#[serde(rename_all = "camelCase")]
+struct PassthroughError {
+    #[serde(skip)]
+    status: StatusCode,
+}
+
+#[derive(HttpError, Serialize)]
+#[http(utoipa)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum ProxyError {
+    #[http(status = 499, reason = "Client Closed Request")]
+    ClientClosed,
+
+    #[http(status = 520)]
+    UnknownUpstream,
+
+    #[http(status = consts::UPSTREAM_TIMEOUT, statuses(504))]
+    Timeout,
+
+    Upstream(UpstreamError),
+}
+
+fn main() -> anyhow::Result<()> {
+    assert_eq!(
+        StatusCode::from_u16(499)?,
+        ProxyError::ClientClosed.status()
+    );
+    assert_eq!(
+        StatusCode::from_u16(520)?,
+        ProxyError::UnknownUpstream.status()
+    );
+    assert!(ProxyError::UnknownUpstream.is_reported());
+    assert_eq!(StatusCode::GATEWAY_TIMEOUT, ProxyError::Timeout.status());
+
+    let error = ProxyError::Upstream(UpstreamError {
+        status: StatusCode::SERVICE_UNAVAILABLE,
+        service: "users".to_owned(),
+    });
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, error.status());
+    assert!(error.is_retryable());
+
+    let error = PassthroughError {
+        status: StatusCode::CONFLICT,
+    };
+    assert_eq!(StatusCode::CONFLICT, error.status());
+
+    let responses = serde_json::to_value(ProxyError::responses())?;
+    assert_eq!(
+        vec!["499", "502", "503", "504", "520"],
+        responses
+            .as_object()
+            .expect("responses")
+            .keys()
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        json!("Client Closed Request"),
+        responses["499"]["description"]
+    );
+    assert_eq!(json!("Status 520"), responses["520"]["description"]);
+    assert_eq!(json!("Bad Gateway"), responses["502"]["description"]);
+    assert_eq!(
+        json!({ "type": "string" }),
+        responses["504"]["content"]["application/json"]["schema"]["properties"]["service"]
+    );
+
+    let responses = serde_json::to_value(PassthroughError::responses())?;
+    assert_eq!(
+        vec!["default"],
+        responses
+            .as_object()
+            .expect("responses")
+            .keys()
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        json!("Error with a status determined at runtime"),
+        responses["default"]["description"]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::anyhow;
+use breach::{HttpError, Level, notify};
+use serde::Serialize;
+use tracing::{field::Empty, info_span};
+use tracing_subscriber::fmt::MakeWriter;
+
+#[derive(HttpError, Serialize)]
+#[http(status = NOT_FOUND)]
+#[serde(rename_all = "camelCase")]
+struct NotFoundError {
+    id: String,
+}
+
+#[derive(HttpError, Serialize)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum GetUserByIdError {
+    NotFound(NotFoundError),
+
+    #[http(status = GONE, level = warn)]
+    Deleted,
+
+    #[http(status = INTERNAL_SERVER_ERROR)]
+    Internal(#[serde(skip)] anyhow::Error),
+}
+
+#[derive(Clone, Default)]
+struct Output(Arc<Mutex<Vec<u8>>>);
+
+impl Output {
+    fn take(&self) -> String {
+        let mut buffer = self.0.lock().expect("output lock");
+
+        String::from_utf8(std::mem::take(&mut buffer)).expect("UTF-8 output")
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().expect("output lock").write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for Output {
+    type Writer = Output;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+fn main() {
+    let output = Output::default();
+    tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::TRACE)
+        .with_ansi(false)
+        .with_writer(output.clone())
+        .init();
+
+    let error = GetUserByIdError::NotFound(NotFoundError { id: "1".to_owned() });
+    assert_eq!(Level::Info, error.level());
+    assert!(error.internal().is_none());
+    notify(&error);
+    let log = output.take();
+    assert!(log.contains("INFO"));
+    assert!(log.contains("http.response.status_code=404"));
+    assert!(log.contains("error.code=\"notFound\""));
+    assert!(log.contains("error.type=\"breach_example_tracing::GetUserByIdError\""));
+
+    let error = GetUserByIdError::Deleted;
+    assert_eq!(Level::Warn, error.level());
+    notify(&error);
+    assert!(output.take().contains("WARN"));
+
+    let error = GetUserByIdError::Internal(
+        anyhow!(io::Error::new(
+            io::ErrorKind::ConnectionRefused,
+            "connection refused"
+        ))
+        .context("database error"),
+    );
+    assert_eq!(Level::Error, error.level());
+    assert_eq!(
+        Some("database error".to_owned()),
+        error.internal().map(ToString::to_string)
+    );
+
+    let span = info_span!(
+        "request",
+        "http.response.status_code" = Empty,
+        "error.code" = Empty
+    );
+    span.in_scope(|| notify(&error));
+    let log = output.take();
+    assert!(log.contains("ERROR"));
+    assert!(log.contains("request{http.response.status_code=500 error.code=\"internal\"}"));
+    assert!(log.contains("error=database error"));
+    assert!(log.contains("connection refused"));
+}
@@ -0,0 +1,104 @@
+use axum::{body::to_bytes, http::StatusCode, response::IntoResponse};
+use breach::HttpError;
+use serde::Serialize;
+use serde_json::json;
+use utoipa::{IntoResponses, ToSchema};
+
+#[derive(HttpError, Serialize, ToSchema)]
+#[http(status = NOT_FOUND, utoipa)]
+#[serde(rename_all = "camelCase")]
+struct NotFoundError {
+    id: String,
+}
+
+#[derive(HttpError, Serialize)]
+#[http(utoipa)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum RouteError {
+    NotFound(NotFoundError),
+
+    #[http(status = SERVICE_UNAVAILABLE)]
+    Unavailable,
+}
+
+/// Error of all routes, boxing the route error to keep results small.
+#[derive(HttpError, Serialize)]
+#[http(transparent, axum, utoipa)]
+#[serde(transparent)]
+struct ApiError(Box<RouteError>);
+
+/// Error with additional context, which is not retryable.
+#[derive(HttpError, Serialize)]
+#[http(transparent, retryable = false)]
+#[serde(transparent)]
+struct ContextError {
+    error: RouteError,
+    #[serde(skip)]
+    context: &'static str,
+}
+
+/// Error with the underlying cause, which is not cloneable.
+#[derive(HttpError, Serialize)]
+#[http(transparent)]
+#[serde(transparent)]
+struct CauseError {
+    error: RouteError,
+    #[serde(skip)]
+    cause: anyhow::Error,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let error = ApiError(Box::new(RouteError::NotFound(NotFoundError {
+        id: "1".to_owned(),
+    })));
+    assert_eq!(StatusCode::NOT_FOUND, error.status());
+    assert_eq!(Some("notFound"), error.code());
+    assert_eq!(
+        vec!["ApiError", "RouteError", "NotFound", "NotFoundError"],
+        error.path()
+    );
+
+    let response = error.into_response();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    assert_eq!(
+        json!({ "code": "notFound", "id": "1" }),
+        serde_json::from_slice::<serde_json::Value>(
+            &to_bytes(response.into_body(), usize::MAX).await?
+        )?
+    );
+
+    assert!(ApiError(Box::new(RouteError::Unavailable)).is_retryable());
+    let error = ContextError {
+        error: RouteError::Unavailable,
+        context: "loading user",
+    };
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, error.status());
+    assert!(!error.is_retryable());
+    assert_eq!("loading user", error.context);
+    assert_eq!(
+        json!({ "code": "unavailable" }),
+        serde_json::to_value(&error)?
+    );
+
+    let error = CauseError {
+        error: RouteError::Unavailable,
+        cause: anyhow::anyhow!("connection refused"),
+    };
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, error.status());
+    assert!(error.masked().is_none());
+    assert_eq!("connection refused", error.cause.to_string());
+    assert_eq!(
+        json!({ "code": "unavailable" }),
+        serde_json::to_value(&error)?
+    );
+
+    let responses = serde_json::to_value(ApiError::responses())?;
+    assert_eq!(serde_json::to_value(RouteError::responses())?, responses);
+
+    Ok(())
+}
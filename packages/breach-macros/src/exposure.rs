@@ -0,0 +1,57 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, quote};
+use syn::{
+    Error, Ident, Result, Token,
+    meta::ParseNestedMeta,
+    parse::{Parse, ParseStream},
+};
+
+#[derive(Clone)]
+pub struct Exposure {
+    ident: Ident,
+}
+
+impl Exposure {
+    pub fn parse_meta(meta: &ParseNestedMeta) -> Result<Self> {
+        if meta.input.peek(Token![=]) {
+            meta.value()?.parse()
+        } else {
+            Ok(Self {
+                ident: Ident::new("Public", Span::call_site()),
+            })
+        }
+    }
+
+    pub fn is_internal(&self) -> bool {
+        self.ident == "Internal"
+    }
+}
+
+impl ToTokens for Exposure {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let ident = &self.ident;
+
+        tokens.extend(quote!(::breach::Exposure::#ident));
+    }
+}
+
+impl Parse for Exposure {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident: Ident = input.parse()?;
+
+        let variant = match ident.to_string().as_str() {
+            "public" => "Public",
+            "internal" => "Internal",
+            _ => {
+                return Err(Error::new(
+                    ident.span(),
+                    "invalid exposure, expected `public` or `internal`",
+                ));
+            }
+        };
+
+        Ok(Self {
+            ident: Ident::new(variant, ident.span().resolved_at(Span::call_site())),
+        })
+    }
+}
@@ -1,14 +1,16 @@
 mod attribute;
+mod bound;
 mod data;
 mod r#enum;
+mod pointer;
 mod r#struct;
 mod r#union;
 
 use proc_macro2::TokenStream;
 use quote::{ToTokens, TokenStreamExt, quote};
-use syn::{DeriveInput, Generics, Ident, Result};
+use syn::{DeriveInput, Generics, Ident, Result, Type, parse_quote};
 
-use crate::http::data::HttpErrorData;
+use crate::http::{bound::Bounds, data::HttpErrorData, pointer::pointee};
 
 pub struct HttpError<'a> {
     ident: &'a Ident,
@@ -29,9 +31,38 @@ impl<'a> HttpError<'a> {
 impl<'a> ToTokens for HttpError<'a> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let ident = &self.ident;
-        let (impl_generics, type_generics, where_clause) = self.generics.split_for_impl();
+        let (impl_generics, type_generics, _) = self.generics.split_for_impl();
+
+        let r#type: Type = parse_quote!(#ident #type_generics);
+        let bounds = Bounds::new(
+            self.generics,
+            &r#type,
+            self.data
+                .attribute()
+                .and_then(|attribute| attribute.bound.as_ref()),
+        );
+        let delegates = self.data.delegates();
+        let payloads = self.data.payloads();
+
+        let where_clause = bounds.where_clause(
+            delegates
+                .iter()
+                .map(|delegate| (delegate, quote!(::breach::HttpError))),
+        );
 
         let status = self.data.status();
+        let code = self.data.code();
+        let reason = self.data.reason();
+        let is_retryable = self.data.is_retryable();
+        let retry_after = self.data.retry_after();
+        let level = self.data.level();
+        let message_key = self.data.message_key();
+        let message_keys = self.data.message_keys();
+        let masked = self.data.masked();
+        let exposure = self.data.exposure();
+        let internal = self.data.internal();
+        let is_reported = self.data.is_reported();
+        let path = self.data.path();
         let hook = self.data.hook();
 
         tokens.append_all(quote! {
@@ -41,28 +72,247 @@ impl<'a> ToTokens for HttpError<'a> {
                     #status
                 }
 
+                fn code(&self) -> ::core::option::Option<&'static str> {
+                    #code
+                }
+
+                fn reason(&self) -> ::core::option::Option<&'static str> {
+                    #reason
+                }
+
+                fn is_retryable(&self) -> bool {
+                    #is_retryable
+                }
+
+                fn retry_after(&self) -> ::core::option::Option<::core::time::Duration> {
+                    #retry_after
+                }
+
+                fn level(&self) -> ::breach::Level {
+                    #level
+                }
+
+                fn message_key(&self) -> ::core::option::Option<&'static str> {
+                    #message_key
+                }
+
+                fn message_keys() -> ::std::vec::Vec<&'static str> {
+                    #message_keys
+                }
+
+                fn masked(&self) -> ::core::option::Option<Self> {
+                    #masked
+                }
+
+                fn exposure(&self) -> ::breach::Exposure {
+                    #exposure
+                }
+
+                fn internal(&self) -> ::core::option::Option<&(dyn ::std::error::Error + 'static)> {
+                    #internal
+                }
+
+                fn is_reported(&self) -> bool {
+                    #is_reported
+                }
+
+                fn path(&self) -> ::std::vec::Vec<&'static str> {
+                    #path
+                }
+
                 fn hook(&self) {
                     #hook
                 }
             }
         });
 
+        if let Some(display) = self.data.display() {
+            let display_where_clause = bounds.where_clause(
+                delegates
+                    .iter()
+                    .map(|delegate| (delegate, quote!(::core::fmt::Display))),
+            );
+            let error_where_clause = bounds.where_clause(
+                delegates
+                    .iter()
+                    .map(|delegate| (delegate, quote!(::std::error::Error + 'static)))
+                    .chain([(&r#type, quote!(::core::fmt::Debug))]),
+            );
+
+            let source = self.data.source();
+
+            tokens.append_all(quote! {
+                #[automatically_derived]
+                impl #impl_generics ::core::fmt::Display for #ident #type_generics #display_where_clause {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        #display
+                    }
+                }
+
+                #[automatically_derived]
+                impl #impl_generics ::std::error::Error for #ident #type_generics #error_where_clause {
+                    fn source(&self) -> ::core::option::Option<&(dyn ::std::error::Error + 'static)> {
+                        #source
+                    }
+                }
+            });
+        }
+
         if let Some(attribute) = self.data.attribute() {
             if attribute.axum {
+                let where_clause = bounds.where_clause(
+                    delegates
+                        .iter()
+                        .map(|delegate| (delegate, quote!(::breach::HttpError)))
+                        .chain([(&r#type, quote!(::serde::Serialize))]),
+                );
+
+                let mut body = match self.data.exposed() {
+                    Some(exposed) => quote!(&#exposed),
+                    None => quote!(error),
+                };
+                if let Some(message_field) = &attribute.message_field {
+                    body = quote!(&::breach::WithMessage::new(#body, error, #message_field));
+                }
+                if attribute.localize {
+                    body = quote!(&::breach::fluent::WithLocalized::new(#body, error));
+                }
+                if attribute.request_id {
+                    body = quote!(&::breach::request_id::WithRequestId::new(#body));
+                }
+
+                let response = if attribute.negotiate {
+                    quote! {{
+                        use ::breach::__private::{ErrorMessage as _, ErrorMessageNone as _};
+
+                        let message = (&::breach::__private::Message(error)).error_message();
+                        let (content_type, body) = ::breach::negotiate::encode(
+                            &::breach::negotiate::ErrorBody::new(error, #body, message),
+                        );
+
+                        (
+                            error.status(),
+                            [(::breach::http::header::CONTENT_TYPE, content_type)],
+                            body,
+                        )
+                            .into_response()
+                    }}
+                } else {
+                    quote!((error.status(), ::axum::Json(#body)).into_response())
+                };
+
                 tokens.append_all(quote! {
                     #[automatically_derived]
                     impl #impl_generics ::axum::response::IntoResponse for #ident #type_generics #where_clause {
                         fn into_response(self) -> ::axum::response::Response {
-                            self.hook();
+                            ::breach::notify(&self);
+
+                            let masked = self.masked();
+                            let error = masked.as_ref().unwrap_or(&self);
 
-                            (self.status(), ::axum::Json(self)).into_response()
+                            let retry_after = error.retry_after();
+                            let mut response = #response;
+
+                            if let ::core::option::Option::Some(retry_after) = retry_after {
+                                response.headers_mut().insert(
+                                    ::breach::http::header::RETRY_AFTER,
+                                    ::breach::http::HeaderValue::from(retry_after.as_secs()),
+                                );
+                            }
+
+                            response
                         }
                     }
                 });
             }
 
+            if attribute.jsonrpsee {
+                let jsonrpc_code = self.data.jsonrpc_code();
+
+                let where_clause = bounds.where_clause(
+                    delegates
+                        .iter()
+                        .map(|delegate| (delegate, quote!(::breach::jsonrpsee::JsonRpcError))),
+                );
+                let from_where_clause = bounds.where_clause(
+                    delegates
+                        .iter()
+                        .map(|delegate| (delegate, quote!(::breach::jsonrpsee::JsonRpcError)))
+                        .chain([(&r#type, quote!(::serde::Serialize))]),
+                );
+
+                tokens.append_all(quote! {
+                    #[automatically_derived]
+                    impl #impl_generics ::breach::jsonrpsee::JsonRpcError for #ident #type_generics #where_clause {
+                        fn jsonrpc_code(&self) -> ::core::option::Option<i32> {
+                            #jsonrpc_code
+                        }
+                    }
+
+                    #[automatically_derived]
+                    impl #impl_generics ::core::convert::From<#ident #type_generics> for ::breach::jsonrpsee::jsonrpsee_types::ErrorObjectOwned #from_where_clause {
+                        fn from(value: #ident #type_generics) -> Self {
+                            ::breach::notify(&value);
+
+                            let masked = ::breach::HttpError::masked(&value);
+                            let error = masked.as_ref().unwrap_or(&value);
+
+                            ::breach::jsonrpsee::error_object(error, &::breach::jsonrpsee::ErrorCodes::default())
+                        }
+                    }
+                });
+            }
+
+            if attribute.leptos {
+                let server_fn_error = self.data.server_fn_error();
+
+                tokens.append_all(quote! {
+                    #[automatically_derived]
+                    impl #impl_generics ::breach::leptos::server_fn::error::FromServerFnError for #ident #type_generics #where_clause {
+                        type Encoder = ::breach::leptos::server_fn::codec::JsonEncoding;
+
+                        fn from_server_fn_error(value: ::breach::leptos::server_fn::error::ServerFnErrorErr) -> Self {
+                            #server_fn_error
+                        }
+
+                        fn ser(&self) -> ::breach::leptos::server_fn::Bytes {
+                            ::breach::leptos::ser(self)
+                        }
+                    }
+                });
+            }
+
+            if attribute.mirror {
+                tokens.append_all(self.data.mirror());
+            }
+
             if attribute.utoipa {
-                let responses = self.data.responses();
+                let where_clause =
+                    bounds.where_clause(
+                        delegates
+                            .iter()
+                            .map(|delegate| (pointee(delegate), quote!(::utoipa::IntoResponses)))
+                            .chain(payloads.iter().map(|payload| {
+                                (pointee(payload), quote!(::utoipa::PartialSchema))
+                            })),
+                    );
+
+                let mut responses = self.data.responses();
+                if attribute.request_id {
+                    responses = quote!(::breach::utoipa::with_request_id(#responses));
+                }
+                if attribute.negotiate {
+                    let content_types = attribute
+                        .content_types
+                        .as_ref()
+                        .map(|content_types| quote!(#content_types))
+                        .unwrap_or_else(|| quote!(::breach::negotiate::CONTENT_TYPES));
+
+                    responses = quote!(::breach::utoipa::with_content_types(
+                        #responses,
+                        #content_types,
+                    ));
+                }
 
                 tokens.append_all(quote! {
                     #[automatically_derived]
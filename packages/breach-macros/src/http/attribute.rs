@@ -1,11 +1,33 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{Attribute, Error, Expr, Result, Type, spanned::Spanned};
-
-use crate::status::Status;
+use syn::{
+    Attribute, Error, Expr, Ident, LitBool, LitStr, Path, Result, Token, Type, WherePredicate,
+    meta::ParseNestedMeta,
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    token,
+};
+
+use crate::{exposure::Exposure, level::Level, message::Message, status::Status};
 
 pub struct HttpErrorAttribute {
     pub status: Option<Status>,
+    pub reason: Option<LitStr>,
+    pub statuses: Vec<Status>,
+    pub default: bool,
+    pub code: Option<LitStr>,
+    pub message: Option<Message>,
+    pub message_key: Option<LitStr>,
+    pub jsonrpc: Option<Expr>,
+    pub retryable: Option<LitBool>,
+    pub level: Option<Level>,
+    pub report: Option<LitBool>,
+    pub expose: Option<Exposure>,
+    pub mask: Option<Ident>,
+    pub remap: Vec<Remap>,
+    pub server_fn: bool,
 }
 
 impl<'a> HttpErrorAttribute {
@@ -32,35 +54,263 @@ impl<'a> HttpErrorAttribute {
 
     pub fn parse(attribute: &'a Attribute) -> Result<Self> {
         let mut status = None;
+        let mut reason = None;
+        let mut statuses = Vec::new();
+        let mut default = false;
+        let mut code = None;
+        let mut message = None;
+        let mut message_key = None;
+        let mut jsonrpc = None;
+        let mut retryable = None;
+        let mut level = None;
+        let mut report = None;
+        let mut expose = None;
+        let mut mask = None;
+        let mut remap = Vec::new();
+        let mut server_fn = false;
 
         attribute.parse_nested_meta(|meta| {
             if meta.path.is_ident("status") {
                 status = Some(meta.value()?.parse()?);
 
+                Ok(())
+            } else if meta.path.is_ident("reason") {
+                reason = Some(meta.value()?.parse()?);
+
+                Ok(())
+            } else if meta.path.is_ident("statuses") {
+                statuses.extend(parse_statuses(&meta)?);
+
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                default = true;
+
+                Ok(())
+            } else if meta.path.is_ident("code") {
+                code = Some(meta.value()?.parse()?);
+
+                Ok(())
+            } else if meta.path.is_ident("message") {
+                message = Some(meta.value()?.parse()?);
+
+                Ok(())
+            } else if meta.path.is_ident("message_key") {
+                message_key = Some(meta.value()?.parse()?);
+
+                Ok(())
+            } else if meta.path.is_ident("jsonrpc") {
+                jsonrpc = Some(meta.value()?.parse()?);
+
+                Ok(())
+            } else if meta.path.is_ident("retryable") {
+                retryable = Some(flag(&meta)?);
+
+                Ok(())
+            } else if meta.path.is_ident("level") {
+                level = Some(meta.value()?.parse()?);
+
+                Ok(())
+            } else if meta.path.is_ident("report") {
+                report = Some(flag(&meta)?);
+
+                Ok(())
+            } else if meta.path.is_ident("expose") {
+                expose = Some(Exposure::parse_meta(&meta)?);
+
+                Ok(())
+            } else if meta.path.is_ident("mask") {
+                mask = Some(meta.value()?.parse()?);
+
+                Ok(())
+            } else if meta.path.is_ident("remap") {
+                let content;
+                parenthesized!(content in meta.input);
+
+                remap.extend(Punctuated::<Remap, Token![,]>::parse_terminated(&content)?);
+
+                Ok(())
+            } else if meta.path.is_ident("server_fn") {
+                server_fn = true;
+
                 Ok(())
             } else {
                 Err(meta.error("unknown parameter"))
             }
         })?;
 
-        Ok(Self { status })
+        Ok(Self {
+            status,
+            reason,
+            statuses,
+            default,
+            code,
+            message,
+            message_key,
+            jsonrpc,
+            retryable,
+            level,
+            report,
+            expose,
+            mask,
+            remap,
+            server_fn,
+        })
     }
 
     pub fn status(&self) -> TokenStream {
         status(self.status.as_ref())
     }
 
+    pub fn remap_status(&self, status: TokenStream) -> TokenStream {
+        if self.remap.is_empty() {
+            return status;
+        }
+
+        self.remap_with(status, |to| to, quote!(status))
+    }
+
+    // Remapped errors behave like errors of the new status, e.g. for the level, other errors use the fallback.
+    pub fn remap_with(
+        &self,
+        status: TokenStream,
+        map: impl Fn(TokenStream) -> TokenStream,
+        fallback: TokenStream,
+    ) -> TokenStream {
+        if self.remap.is_empty() {
+            return fallback;
+        }
+
+        let arms = self.remap.iter().map(|remap| {
+            let from = self::status(Some(&remap.from));
+            let to = map(self::status(Some(&remap.to)));
+
+            quote!(if status == #from { #to })
+        });
+
+        quote!({
+            let status = #status;
+
+            #( #arms else )* { #fallback }
+        })
+    }
+
+    pub fn remap_responses(&self, responses: TokenStream) -> TokenStream {
+        if self.remap.is_empty() {
+            return responses;
+        }
+
+        let pairs = self.remap.iter().map(|remap| {
+            let from = self::status(Some(&remap.from));
+            let to = self::status(Some(&remap.to));
+
+            quote!((#from, #to))
+        });
+
+        quote!(::breach::utoipa::remap_responses(#responses, &[ #( #pairs ),* ]))
+    }
+
+    pub fn jsonrpc_code(&self) -> TokenStream {
+        jsonrpc_code(self.jsonrpc.as_ref())
+    }
+
     pub fn responses(&self, r#type: Option<TokenStream>) -> TokenStream {
-        responses(self.status.as_ref(), r#type)
+        responses(
+            self.status.as_ref(),
+            self.reason.as_ref(),
+            &self.statuses,
+            self.default,
+            r#type,
+        )
+    }
+}
+
+pub struct Remap {
+    pub from: Status,
+    pub to: Status,
+}
+
+impl Parse for Remap {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let from = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let to = input.parse()?;
+
+        Ok(Self { from, to })
+    }
+}
+
+pub struct HttpErrorFieldAttribute {
+    pub expose: Option<Exposure>,
+    pub status: bool,
+    pub source: bool,
+}
+
+impl HttpErrorFieldAttribute {
+    pub fn parse_slice(input: &[Attribute]) -> Result<Self> {
+        let mut expose = None;
+        let mut status = false;
+        let mut source = false;
+
+        for attribute in input {
+            if !attribute.meta.path().is_ident("http") {
+                continue;
+            }
+
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("expose") {
+                    expose = Some(Exposure::parse_meta(&meta)?);
+
+                    Ok(())
+                } else if meta.path.is_ident("status") {
+                    status = true;
+
+                    Ok(())
+                } else if meta.path.is_ident("source") {
+                    source = true;
+
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown parameter"))
+                }
+            })?;
+        }
+
+        Ok(Self {
+            expose,
+            status,
+            source,
+        })
     }
 }
 
 pub struct HttpErrorDataAttribute {
     pub status: Option<Status>,
+    pub reason: Option<LitStr>,
+    pub statuses: Vec<Status>,
+    pub default: bool,
+    pub code: Option<LitStr>,
+    pub message: Option<Message>,
+    pub message_field: Option<LitStr>,
+    pub message_key: Option<LitStr>,
+    pub jsonrpc: Option<Expr>,
     pub base: Option<Type>,
     pub hook: Option<Expr>,
+    pub bound: Option<Punctuated<WherePredicate, Token![,]>>,
+    pub retryable: Option<LitBool>,
+    pub retry_after: Option<Expr>,
+    pub level: Option<Level>,
+    pub report: Option<LitBool>,
+    pub expose: Option<Exposure>,
+    pub request_id: bool,
+    pub localize: bool,
+    pub negotiate: bool,
+    pub content_types: Option<Path>,
     pub axum: bool,
+    pub jsonrpsee: bool,
+    pub leptos: bool,
+    pub mirror: bool,
     pub utoipa: bool,
+    pub transparent: bool,
 }
 
 impl<'a> HttpErrorDataAttribute {
@@ -87,15 +337,69 @@ impl<'a> HttpErrorDataAttribute {
 
     pub fn parse(attribute: &'a Attribute) -> Result<Self> {
         let mut status = None;
+        let mut reason = None;
+        let mut statuses = Vec::new();
+        let mut default = false;
+        let mut code = None;
+        let mut message = None;
+        let mut message_field: Option<LitStr> = None;
+        let mut message_key = None;
+        let mut jsonrpc = None;
         let mut base = None;
         let mut hook = None;
+        let mut bound = None;
+        let mut retryable = None;
+        let mut retry_after = None;
+        let mut level = None;
+        let mut report = None;
+        let mut expose = None;
+        let mut request_id = false;
+        let mut localize = false;
+        let mut negotiate = false;
+        let mut content_types = None;
         let mut axum = false;
+        let mut jsonrpsee = false;
+        let mut leptos = false;
+        let mut mirror = false;
         let mut utoipa = false;
+        let mut transparent = false;
 
         attribute.parse_nested_meta(|meta| {
             if meta.path.is_ident("status") {
                 status = Some(meta.value()?.parse()?);
 
+                Ok(())
+            } else if meta.path.is_ident("reason") {
+                reason = Some(meta.value()?.parse()?);
+
+                Ok(())
+            } else if meta.path.is_ident("statuses") {
+                statuses.extend(parse_statuses(&meta)?);
+
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                default = true;
+
+                Ok(())
+            } else if meta.path.is_ident("code") {
+                code = Some(meta.value()?.parse()?);
+
+                Ok(())
+            } else if meta.path.is_ident("message") {
+                message = Some(meta.value()?.parse()?);
+
+                Ok(())
+            } else if meta.path.is_ident("message_key") {
+                message_key = Some(meta.value()?.parse()?);
+
+                Ok(())
+            } else if meta.path.is_ident("jsonrpc") {
+                jsonrpc = Some(meta.value()?.parse()?);
+
+                Ok(())
+            } else if meta.path.is_ident("message_field") {
+                message_field = Some(meta.value()?.parse()?);
+
                 Ok(())
             } else if meta.path.is_ident("base") {
                 base = Some(meta.value()?.parse()?);
@@ -104,26 +408,126 @@ impl<'a> HttpErrorDataAttribute {
             } else if meta.path.is_ident("hook") {
                 hook = Some(meta.value()?.parse()?);
 
+                Ok(())
+            } else if meta.path.is_ident("bound") {
+                let value: LitStr = meta.value()?.parse()?;
+                bound = Some(value.parse_with(Punctuated::parse_terminated)?);
+
+                Ok(())
+            } else if meta.path.is_ident("retryable") {
+                retryable = Some(flag(&meta)?);
+
+                Ok(())
+            } else if meta.path.is_ident("retry_after") {
+                retry_after = Some(meta.value()?.parse()?);
+
+                Ok(())
+            } else if meta.path.is_ident("level") {
+                level = Some(meta.value()?.parse()?);
+
+                Ok(())
+            } else if meta.path.is_ident("report") {
+                report = Some(flag(&meta)?);
+
+                Ok(())
+            } else if meta.path.is_ident("expose") {
+                expose = Some(Exposure::parse_meta(&meta)?);
+
+                Ok(())
+            } else if meta.path.is_ident("request_id") {
+                request_id = true;
+
+                Ok(())
+            } else if meta.path.is_ident("localize") {
+                localize = true;
+
+                Ok(())
+            } else if meta.path.is_ident("negotiate") {
+                negotiate = true;
+
+                // Constant with the content types of the configured encoders, documented with `utoipa`.
+                if meta.input.peek(token::Paren) {
+                    meta.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("content_types") {
+                            content_types = Some(meta.value()?.parse()?);
+
+                            Ok(())
+                        } else {
+                            Err(meta.error("unknown parameter"))
+                        }
+                    })?;
+                }
+
                 Ok(())
             } else if meta.path.is_ident("axum") {
                 axum = true;
 
+                Ok(())
+            } else if meta.path.is_ident("jsonrpsee") {
+                jsonrpsee = true;
+
+                Ok(())
+            } else if meta.path.is_ident("leptos") {
+                leptos = true;
+
+                Ok(())
+            } else if meta.path.is_ident("mirror") {
+                mirror = true;
+
                 Ok(())
             } else if meta.path.is_ident("utoipa") {
                 utoipa = true;
 
+                Ok(())
+            } else if meta.path.is_ident("transparent") {
+                transparent = true;
+
                 Ok(())
             } else {
                 Err(meta.error("unknown parameter"))
             }
         })?;
 
+        // The localized message is flattened into the same body.
+        if let Some(message_field) = &message_field
+            && localize
+            && ["message", "title"].contains(&message_field.value().as_str())
+        {
+            return Err(Error::new(
+                message_field.span(),
+                "`message_field` conflicts with the `title` and `message` members of `localize`, \
+                use another field (e.g. `detail`)",
+            ));
+        }
+
         Ok(Self {
             status,
+            reason,
+            statuses,
+            default,
+            code,
+            message,
+            message_field,
+            message_key,
+            jsonrpc,
             base,
             hook,
+            bound,
+            retryable,
+            retry_after,
+            level,
+            report,
+            expose,
+            request_id,
+            localize,
+            negotiate,
+            content_types,
             axum,
+            jsonrpsee,
+            leptos,
+            mirror,
             utoipa,
+            transparent,
         })
     }
 
@@ -131,8 +535,66 @@ impl<'a> HttpErrorDataAttribute {
         status(self.status.as_ref())
     }
 
+    pub fn code(&self) -> TokenStream {
+        if let Some(code) = &self.code {
+            quote!(::core::option::Option::Some(#code))
+        } else {
+            quote!(::core::option::Option::None)
+        }
+    }
+
+    pub fn jsonrpc_code(&self) -> TokenStream {
+        jsonrpc_code(self.jsonrpc.as_ref())
+    }
+
     pub fn responses(&self, r#type: Option<TokenStream>) -> TokenStream {
-        responses(self.status.as_ref(), r#type)
+        responses(
+            self.status.as_ref(),
+            self.reason.as_ref(),
+            &self.statuses,
+            self.default,
+            r#type,
+        )
+    }
+
+    pub fn is_retryable(&self) -> TokenStream {
+        if let Some(retryable) = &self.retryable {
+            quote!(#retryable)
+        } else {
+            quote!(::breach::is_retryable_status(self.status()))
+        }
+    }
+
+    pub fn retry_after(&self) -> TokenStream {
+        if let Some(retry_after) = &self.retry_after {
+            quote!((#retry_after)(self))
+        } else {
+            quote!(::core::option::Option::None)
+        }
+    }
+
+    pub fn level(&self) -> TokenStream {
+        if let Some(level) = &self.level {
+            quote!(#level)
+        } else {
+            quote!(::breach::Level::from_status(self.status()))
+        }
+    }
+
+    pub fn exposure(&self) -> TokenStream {
+        if let Some(expose) = &self.expose {
+            quote!(#expose)
+        } else {
+            quote!(::breach::Exposure::Public)
+        }
+    }
+
+    pub fn is_reported(&self) -> TokenStream {
+        if let Some(report) = &self.report {
+            quote!(#report)
+        } else {
+            quote!(self.status().is_server_error())
+        }
     }
 
     pub fn hook(&self) -> TokenStream {
@@ -146,19 +608,117 @@ impl<'a> HttpErrorDataAttribute {
     }
 }
 
+fn flag(meta: &ParseNestedMeta) -> Result<LitBool> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse()
+    } else {
+        Ok(LitBool::new(true, meta.path.span()))
+    }
+}
+
+fn parse_statuses(meta: &ParseNestedMeta) -> Result<Vec<Status>> {
+    let content;
+    parenthesized!(content in meta.input);
+
+    Punctuated::<Status, Token![,]>::parse_terminated(&content)?
+        .into_iter()
+        .map(|status| match status.code {
+            Some(_) => Ok(status),
+            None => Err(Error::new(
+                status.span(),
+                "expected a status code literal or constant",
+            )),
+        })
+        .collect()
+}
+
 fn status(status: Option<&Status>) -> TokenStream {
     if let Some(status) = status {
-        let status = status.as_ident();
-
-        quote!(::breach::http::StatusCode::#status)
+        status.as_status_code()
     } else {
         quote!(compile_error!("missing `#[http(status = ..)]` attribute"))
     }
 }
 
-fn responses(status: Option<&Status>, r#type: Option<TokenStream>) -> TokenStream {
+// Payloads are exposed to clients by default, which is rarely intended for server errors. Statuses determined at
+// runtime may be server errors, unless `statuses(..)` lists them.
+pub fn check_server_error_payload(
+    span: Span,
+    kind: &str,
+    status: Option<&Status>,
+    statuses: &[Status],
+) -> Result<()> {
+    let is_server_error = status
+        .into_iter()
+        .chain(statuses)
+        .filter_map(|status| status.code)
+        .any(|code| code.is_server_error());
+    let is_runtime = status.is_some_and(|status| status.code.is_none()) && statuses.is_empty();
+
+    if is_server_error {
+        Err(Error::new(
+            span,
+            format!(
+                "payload of server error {kind} is exposed to clients, \
+                use `#[serde(skip)]` or `#[http(expose = internal)]` to hide it \
+                or `#[http(expose)]` to expose it"
+            ),
+        ))
+    } else if is_runtime {
+        Err(Error::new(
+            span,
+            format!(
+                "payload of {kind} with a status determined at runtime may be exposed to clients in server errors, \
+                list the possible statuses with `statuses(..)`, \
+                use `#[serde(skip)]` or `#[http(expose = internal)]` to hide it \
+                or `#[http(expose)]` to expose it"
+            ),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+// Description of `default` responses without a reason phrase, as in `breach::utoipa`.
+const DEFAULT_DESCRIPTION: &str = "Error with a status determined at runtime";
+
+// `std::error::Error` of the value, if it is (or dereferences to) one.
+pub fn internal_error(value: TokenStream) -> TokenStream {
+    quote!({
+        #[allow(unused_imports)]
+        use ::breach::__private::{InternalError, InternalErrorNone, InternalErrorRef};
+
+        (&&::breach::__private::Internal(#value)).internal_error()
+    })
+}
+
+fn jsonrpc_code(jsonrpc: Option<&Expr>) -> TokenStream {
+    if let Some(jsonrpc) = jsonrpc {
+        quote!(::core::option::Option::Some(#jsonrpc))
+    } else {
+        quote!(::core::option::Option::None)
+    }
+}
+
+fn responses(
+    status: Option<&Status>,
+    reason: Option<&LitStr>,
+    statuses: &[Status],
+    default: bool,
+    r#type: Option<TokenStream>,
+) -> TokenStream {
     if let Some(status) = status {
-        let code = status.code.as_str();
+        // Statuses determined at runtime are documented with `statuses(..)` or `default`.
+        let codes = if statuses.is_empty() {
+            status.code.into_iter().collect::<Vec<_>>()
+        } else {
+            statuses.iter().filter_map(|status| status.code).collect()
+        };
+        if codes.is_empty() && !default {
+            return quote!(compile_error!(
+                "status is determined at runtime, use `#[http(statuses(..))]` or `#[http(default)]` to document it"
+            ));
+        }
 
         let content = r#type.map(|r#type| {
             // TODO: Attempt to infer content type from schema?
@@ -172,16 +732,45 @@ fn responses(status: Option<&Status>, r#type: Option<TokenStream>) -> TokenStrea
             }
         });
 
+        let responses = codes
+            .into_iter()
+            .map(|code| {
+                (
+                    code.as_str().to_owned(),
+                    reason
+                        .map(LitStr::value)
+                        .or_else(|| code.canonical_reason().map(str::to_owned))
+                        // OpenAPI requires a description, non-standard status codes have no canonical reason.
+                        .unwrap_or_else(|| format!("Status {}", code.as_str())),
+                )
+            })
+            .chain(default.then(|| {
+                (
+                    "default".to_owned(),
+                    reason
+                        .map(LitStr::value)
+                        .unwrap_or_else(|| DEFAULT_DESCRIPTION.to_owned()),
+                )
+            }))
+            .map(|(code, description)| {
+                let description = quote!(.description(#description));
+
+                quote! {
+                    (
+                        #code.to_owned(),
+                        ::utoipa::openapi::RefOr::T(
+                            ::utoipa::openapi::response::ResponseBuilder::new()
+                                #description
+                                #content
+                                .build()
+                        ),
+                    )
+                }
+            });
+
         quote! {
             ::std::collections::BTreeMap::from_iter([
-                (
-                    #code.to_owned(),
-                    ::utoipa::openapi::RefOr::T(
-                        ::utoipa::openapi::response::ResponseBuilder::new()
-                            #content
-                            .build()
-                    ),
-                ),
+                #( #responses ),*
             ])
         }
     } else {
@@ -0,0 +1,88 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{ToTokens, quote};
+use syn::{GenericParam, Generics, Ident, Token, Type, WherePredicate, punctuated::Punctuated};
+
+pub struct Bounds<'a> {
+    generics: &'a Generics,
+    r#type: &'a Type,
+    bound: Option<&'a Punctuated<WherePredicate, Token![,]>>,
+}
+
+impl<'a> Bounds<'a> {
+    pub fn new(
+        generics: &'a Generics,
+        r#type: &'a Type,
+        bound: Option<&'a Punctuated<WherePredicate, Token![,]>>,
+    ) -> Self {
+        Self {
+            generics,
+            r#type,
+            bound,
+        }
+    }
+
+    pub fn where_clause<'b>(
+        &self,
+        bounds: impl IntoIterator<Item = (&'b Type, TokenStream)>,
+    ) -> TokenStream {
+        let params = self
+            .generics
+            .params
+            .iter()
+            .filter_map(|param| match param {
+                GenericParam::Type(param) => Some(&param.ident),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut predicates = self
+            .generics
+            .where_clause
+            .iter()
+            .flat_map(|where_clause| where_clause.predicates.iter())
+            .map(ToTokens::to_token_stream)
+            .collect::<Vec<_>>();
+
+        // Like serde, explicit bounds replace the inferred bounds for types involving type parameters.
+        // Bounds on the type itself (e.g. `Self: Serialize` of `IntoResponse`) are kept.
+        if let Some(bound) = self.bound {
+            predicates.extend(bound.iter().map(ToTokens::to_token_stream));
+        }
+
+        if !params.is_empty() {
+            let self_type = self.r#type.to_token_stream().to_string();
+
+            for (r#type, r#trait) in bounds {
+                let is_self = matches!(r#type, Type::Path(path) if path.qself.is_none() && path.path.is_ident("Self"))
+                    || r#type.to_token_stream().to_string() == self_type;
+                let is_inferred =
+                    self.bound.is_none() && contains(r#type.to_token_stream(), &params);
+
+                if is_self || is_inferred {
+                    let predicate = quote!(#r#type: #r#trait);
+
+                    if !predicates
+                        .iter()
+                        .any(|existing| existing.to_string() == predicate.to_string())
+                    {
+                        predicates.push(predicate);
+                    }
+                }
+            }
+        }
+
+        if predicates.is_empty() {
+            TokenStream::new()
+        } else {
+            quote!(where #( #predicates ),*)
+        }
+    }
+}
+
+fn contains(tokens: TokenStream, params: &[&Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => params.iter().any(|param| **param == ident),
+        TokenTree::Group(group) => contains(group.stream(), params),
+        _ => false,
+    })
+}
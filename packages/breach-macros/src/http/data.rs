@@ -1,5 +1,5 @@
 use proc_macro2::TokenStream;
-use syn::{Data, DeriveInput, Result};
+use syn::{Data, DeriveInput, Result, Type};
 
 use crate::http::{
     attribute::HttpErrorDataAttribute, r#enum::HttpErrorEnum, r#struct::HttpErrorStruct,
@@ -7,7 +7,7 @@ use crate::http::{
 };
 
 pub enum HttpErrorData<'a> {
-    Struct(HttpErrorStruct),
+    Struct(HttpErrorStruct<'a>),
     Enum(HttpErrorEnum<'a>),
     Union(HttpErrorUnion),
 }
@@ -29,6 +29,22 @@ impl<'a> HttpErrorData<'a> {
         }
     }
 
+    pub fn delegates(&self) -> Vec<Type> {
+        match self {
+            HttpErrorData::Struct(r#struct) => r#struct.delegates(),
+            HttpErrorData::Enum(r#enum) => r#enum.delegates(),
+            HttpErrorData::Union(r#union) => r#union.delegates(),
+        }
+    }
+
+    pub fn payloads(&self) -> Vec<Type> {
+        match self {
+            HttpErrorData::Struct(r#struct) => r#struct.payloads(),
+            HttpErrorData::Enum(r#enum) => r#enum.payloads(),
+            HttpErrorData::Union(r#union) => r#union.payloads(),
+        }
+    }
+
     pub fn status(&self) -> TokenStream {
         match self {
             HttpErrorData::Struct(r#struct) => r#struct.status(),
@@ -37,6 +53,134 @@ impl<'a> HttpErrorData<'a> {
         }
     }
 
+    pub fn code(&self) -> TokenStream {
+        match self {
+            HttpErrorData::Struct(r#struct) => r#struct.code(),
+            HttpErrorData::Enum(r#enum) => r#enum.code(),
+            HttpErrorData::Union(r#union) => r#union.code(),
+        }
+    }
+
+    pub fn message_key(&self) -> TokenStream {
+        match self {
+            HttpErrorData::Struct(r#struct) => r#struct.message_key(),
+            HttpErrorData::Enum(r#enum) => r#enum.message_key(),
+            HttpErrorData::Union(r#union) => r#union.message_key(),
+        }
+    }
+
+    pub fn message_keys(&self) -> TokenStream {
+        match self {
+            HttpErrorData::Struct(r#struct) => r#struct.message_keys(),
+            HttpErrorData::Enum(r#enum) => r#enum.message_keys(),
+            HttpErrorData::Union(r#union) => r#union.message_keys(),
+        }
+    }
+
+    pub fn jsonrpc_code(&self) -> TokenStream {
+        match self {
+            HttpErrorData::Struct(r#struct) => r#struct.jsonrpc_code(),
+            HttpErrorData::Enum(r#enum) => r#enum.jsonrpc_code(),
+            HttpErrorData::Union(r#union) => r#union.jsonrpc_code(),
+        }
+    }
+
+    pub fn is_retryable(&self) -> TokenStream {
+        match self {
+            HttpErrorData::Struct(r#struct) => r#struct.is_retryable(),
+            HttpErrorData::Enum(r#enum) => r#enum.is_retryable(),
+            HttpErrorData::Union(r#union) => r#union.is_retryable(),
+        }
+    }
+
+    pub fn reason(&self) -> TokenStream {
+        match self {
+            HttpErrorData::Struct(r#struct) => r#struct.reason(),
+            HttpErrorData::Enum(r#enum) => r#enum.reason(),
+            HttpErrorData::Union(r#union) => r#union.reason(),
+        }
+    }
+
+    pub fn retry_after(&self) -> TokenStream {
+        match self {
+            HttpErrorData::Struct(r#struct) => r#struct.retry_after(),
+            HttpErrorData::Enum(r#enum) => r#enum.retry_after(),
+            HttpErrorData::Union(r#union) => r#union.retry_after(),
+        }
+    }
+
+    pub fn level(&self) -> TokenStream {
+        match self {
+            HttpErrorData::Struct(r#struct) => r#struct.level(),
+            HttpErrorData::Enum(r#enum) => r#enum.level(),
+            HttpErrorData::Union(r#union) => r#union.level(),
+        }
+    }
+
+    pub fn masked(&self) -> TokenStream {
+        match self {
+            HttpErrorData::Struct(r#struct) => r#struct.masked(),
+            HttpErrorData::Enum(r#enum) => r#enum.masked(),
+            HttpErrorData::Union(r#union) => r#union.masked(),
+        }
+    }
+
+    pub fn exposure(&self) -> TokenStream {
+        match self {
+            HttpErrorData::Struct(r#struct) => r#struct.exposure(),
+            HttpErrorData::Enum(r#enum) => r#enum.exposure(),
+            HttpErrorData::Union(r#union) => r#union.exposure(),
+        }
+    }
+
+    pub fn exposed(&self) -> Option<TokenStream> {
+        match self {
+            HttpErrorData::Struct(r#struct) => r#struct.exposed(),
+            HttpErrorData::Enum(r#enum) => r#enum.exposed(),
+            HttpErrorData::Union(r#union) => r#union.exposed(),
+        }
+    }
+
+    pub fn internal(&self) -> TokenStream {
+        match self {
+            HttpErrorData::Struct(r#struct) => r#struct.internal(),
+            HttpErrorData::Enum(r#enum) => r#enum.internal(),
+            HttpErrorData::Union(r#union) => r#union.internal(),
+        }
+    }
+
+    pub fn display(&self) -> Option<TokenStream> {
+        match self {
+            HttpErrorData::Struct(r#struct) => r#struct.display(),
+            HttpErrorData::Enum(r#enum) => r#enum.display(),
+            HttpErrorData::Union(r#union) => r#union.display(),
+        }
+    }
+
+    pub fn source(&self) -> TokenStream {
+        match self {
+            HttpErrorData::Struct(r#struct) => r#struct.source(),
+            HttpErrorData::Enum(r#enum) => r#enum.source(),
+            HttpErrorData::Union(r#union) => r#union.source(),
+        }
+    }
+
+    pub fn is_reported(&self) -> TokenStream {
+        match self {
+            HttpErrorData::Struct(r#struct) => r#struct.is_reported(),
+            HttpErrorData::Enum(r#enum) => r#enum.is_reported(),
+            HttpErrorData::Union(r#union) => r#union.is_reported(),
+        }
+    }
+
+    pub fn path(&self) -> TokenStream {
+        match self {
+            HttpErrorData::Struct(r#struct) => r#struct.path(),
+            HttpErrorData::Enum(r#enum) => r#enum.path(),
+            HttpErrorData::Union(r#union) => r#union.path(),
+        }
+    }
+
     pub fn responses(&self) -> TokenStream {
         match self {
             HttpErrorData::Struct(r#struct) => r#struct.responses(),
@@ -45,6 +189,22 @@ impl<'a> HttpErrorData<'a> {
         }
     }
 
+    pub fn server_fn_error(&self) -> TokenStream {
+        match self {
+            HttpErrorData::Struct(r#struct) => r#struct.server_fn_error(),
+            HttpErrorData::Enum(r#enum) => r#enum.server_fn_error(),
+            HttpErrorData::Union(r#union) => r#union.server_fn_error(),
+        }
+    }
+
+    pub fn mirror(&self) -> TokenStream {
+        match self {
+            HttpErrorData::Struct(r#struct) => r#struct.mirror(),
+            HttpErrorData::Enum(r#enum) => r#enum.mirror(),
+            HttpErrorData::Union(r#union) => r#union.mirror(),
+        }
+    }
+
     pub fn hook(&self) -> TokenStream {
         match self {
             HttpErrorData::Struct(r#struct) => r#struct.hook(),
@@ -1,27 +1,95 @@
 use proc_macro2::TokenStream;
-use quote::{ToTokens, quote};
-use syn::{DataEnum, DeriveInput, Error, Field, Fields, Ident, Result, Variant, spanned::Spanned};
+use quote::{ToTokens, format_ident, quote};
+use syn::{
+    Attribute, DataEnum, DeriveInput, Error, Field, Fields, Generics, Ident, LitBool, Member, Meta,
+    Result, Token, Type, Variant, Visibility, punctuated::Punctuated, spanned::Spanned,
+};
 
-use crate::http::attribute::{HttpErrorAttribute, HttpErrorDataAttribute};
+use crate::{
+    exposure::Exposure,
+    http::{
+        attribute::{
+            HttpErrorAttribute, HttpErrorDataAttribute, HttpErrorFieldAttribute,
+            check_server_error_payload, internal_error,
+        },
+        pointer::pointee,
+    },
+    level::Level,
+    message::Message,
+    serde::{RenameRule, SerdeAttribute},
+};
 
 pub struct HttpErrorEnum<'a> {
     ident: &'a Ident,
+    vis: &'a Visibility,
+    generics: &'a Generics,
     variants: Vec<HttpErrorEnumVariant<'a>>,
     attribute: Option<HttpErrorDataAttribute>,
+    tag: Option<String>,
 }
 
 impl<'a> HttpErrorEnum<'a> {
     pub fn parse(input: &'a DeriveInput, data: &'a DataEnum) -> Result<Self> {
+        let serde = SerdeAttribute::parse_slice(&input.attrs)?;
+
         let mut result = HttpErrorEnum {
             ident: &input.ident,
+            vis: &input.vis,
+            generics: &input.generics,
             variants: Vec::with_capacity(data.variants.len()),
             attribute: HttpErrorDataAttribute::parse_slice(&input.attrs)?,
+            tag: serde.tag,
         };
 
+        if let Some(message_key) = result
+            .attribute
+            .as_ref()
+            .and_then(|attribute| attribute.message_key.as_ref())
+        {
+            return Err(Error::new(
+                message_key.span(),
+                "`message_key` is only supported on structs and variants",
+            ));
+        }
+
+        let expose = result
+            .attribute
+            .as_ref()
+            .and_then(|attribute| attribute.expose.as_ref());
+
         for variant in &data.variants {
-            result
-                .variants
-                .push(HttpErrorEnumVariant::parse(result.ident, variant)?);
+            result.variants.push(HttpErrorEnumVariant::parse(
+                result.ident,
+                variant,
+                serde.rename_all,
+                expose,
+            )?);
+        }
+
+        for variant in &result.variants {
+            let Some(mask) = variant.mask() else {
+                continue;
+            };
+
+            match result.variant(mask) {
+                None => return Err(Error::new(mask.span(), "unknown variant")),
+                Some(target) if target.mask().is_some() || target.ident == variant.ident => {
+                    return Err(Error::new(mask.span(), "variant must not be masked itself"));
+                }
+                Some(_) => {}
+            }
+        }
+
+        if let Some(variant) = result
+            .variants
+            .iter()
+            .filter(|variant| variant.is_server_fn())
+            .nth(1)
+        {
+            return Err(Error::new(
+                variant.ident.span(),
+                "only a single `#[http(server_fn)]` variant is allowed",
+            ));
         }
 
         Ok(result)
@@ -31,6 +99,26 @@ impl<'a> HttpErrorEnum<'a> {
         self.attribute.as_ref()
     }
 
+    fn variant(&self, ident: &Ident) -> Option<&HttpErrorEnumVariant<'a>> {
+        self.variants.iter().find(|variant| variant.ident == ident)
+    }
+
+    pub fn delegates(&self) -> Vec<Type> {
+        self.variants
+            .iter()
+            .filter(|variant| variant.is_delegating())
+            .filter_map(|variant| variant.field.map(|field| field.ty.clone()))
+            .collect()
+    }
+
+    pub fn payloads(&self) -> Vec<Type> {
+        self.variants
+            .iter()
+            .filter(|variant| !variant.is_delegating())
+            .filter_map(|variant| variant.field.map(|field| field.ty.clone()))
+            .collect()
+    }
+
     pub fn status(&self) -> TokenStream {
         let arms = self.variants.iter().map(|variant| variant.status());
 
@@ -41,6 +129,229 @@ impl<'a> HttpErrorEnum<'a> {
         }
     }
 
+    pub fn code(&self) -> TokenStream {
+        let arms = self.variants.iter().map(|variant| variant.code());
+
+        quote! {
+            match &self {
+                #( #arms ),*
+            }
+        }
+    }
+
+    pub fn message_key(&self) -> TokenStream {
+        let arms = self.variants.iter().map(|variant| variant.message_key());
+
+        quote! {
+            match &self {
+                #( #arms ),*
+            }
+        }
+    }
+
+    pub fn message_keys(&self) -> TokenStream {
+        // Masked variants are never sent to clients.
+        let pushes = self
+            .variants
+            .iter()
+            .filter(|variant| variant.mask().is_none())
+            .map(|variant| {
+                let cfgs = &variant.cfgs;
+                let keys = variant.message_keys();
+
+                quote! {
+                    #( #cfgs )*
+                    keys.extend(#keys);
+                }
+            });
+
+        quote!({
+            let mut keys = ::std::vec::Vec::new();
+            #( #pushes )*
+            keys
+        })
+    }
+
+    pub fn jsonrpc_code(&self) -> TokenStream {
+        let arms = self.variants.iter().map(|variant| variant.jsonrpc_code());
+
+        quote! {
+            match &self {
+                #( #arms ),*
+            }
+        }
+    }
+
+    pub fn is_retryable(&self) -> TokenStream {
+        let retryable = self.retryable();
+        let arms = self
+            .variants
+            .iter()
+            .map(|variant| variant.is_retryable(retryable));
+
+        quote! {
+            match &self {
+                #( #arms ),*
+            }
+        }
+    }
+
+    pub fn retry_after(&self) -> TokenStream {
+        if let Some(attribute) = self
+            .attribute
+            .as_ref()
+            .filter(|attribute| attribute.retry_after.is_some())
+        {
+            return attribute.retry_after();
+        }
+
+        let arms = self.variants.iter().map(|variant| variant.retry_after());
+
+        quote! {
+            match &self {
+                #( #arms ),*
+            }
+        }
+    }
+
+    pub fn reason(&self) -> TokenStream {
+        let arms = self.variants.iter().map(|variant| variant.reason());
+
+        quote! {
+            match &self {
+                #( #arms ),*
+            }
+        }
+    }
+
+    pub fn level(&self) -> TokenStream {
+        let level = self
+            .attribute
+            .as_ref()
+            .and_then(|attribute| attribute.level.as_ref());
+        let arms = self.variants.iter().map(|variant| variant.level(level));
+
+        quote! {
+            match &self {
+                #( #arms ),*
+            }
+        }
+    }
+
+    pub fn exposure(&self) -> TokenStream {
+        let arms = self.variants.iter().map(|variant| variant.exposure());
+
+        quote! {
+            match &self {
+                #( #arms ),*
+            }
+        }
+    }
+
+    pub fn exposed(&self) -> Option<TokenStream> {
+        let exposed = self
+            .attribute
+            .as_ref()
+            .is_some_and(|attribute| attribute.expose.is_some())
+            || self
+                .variants
+                .iter()
+                .any(|variant| variant.exposure.is_some() || variant.is_delegating());
+
+        exposed.then(|| {
+            let tag = match &self.tag {
+                Some(tag) => quote!(::core::option::Option::Some(#tag)),
+                None => quote!(::core::option::Option::None),
+            };
+
+            quote!(::breach::Exposed::new(error, #tag))
+        })
+    }
+
+    pub fn masked(&self) -> TokenStream {
+        let arms = self
+            .variants
+            .iter()
+            .map(|variant| variant.masked(variant.mask().and_then(|mask| self.variant(mask))));
+
+        quote! {
+            match &self {
+                #( #arms ),*
+            }
+        }
+    }
+
+    pub fn internal(&self) -> TokenStream {
+        let arms = self.variants.iter().map(|variant| variant.internal());
+
+        quote! {
+            match &self {
+                #( #arms ),*
+            }
+        }
+    }
+
+    pub fn display(&self) -> Option<TokenStream> {
+        let message = self
+            .attribute
+            .as_ref()
+            .and_then(|attribute| attribute.message.as_ref());
+
+        if message.is_none()
+            && self
+                .variants
+                .iter()
+                .all(|variant| variant.message().is_none())
+        {
+            return None;
+        }
+
+        let arms = self.variants.iter().map(|variant| variant.display(message));
+
+        Some(quote! {
+            match &self {
+                #( #arms ),*
+            }
+        })
+    }
+
+    pub fn source(&self) -> TokenStream {
+        let arms = self.variants.iter().map(|variant| variant.source());
+
+        quote! {
+            match &self {
+                #( #arms ),*
+            }
+        }
+    }
+
+    pub fn is_reported(&self) -> TokenStream {
+        let report = self
+            .attribute
+            .as_ref()
+            .and_then(|attribute| attribute.report.as_ref());
+        let arms = self
+            .variants
+            .iter()
+            .map(|variant| variant.is_reported(report));
+
+        quote! {
+            match &self {
+                #( #arms ),*
+            }
+        }
+    }
+
+    pub fn path(&self) -> TokenStream {
+        let arms = self.variants.iter().map(|variant| variant.path());
+
+        quote! {
+            match &self {
+                #( #arms ),*
+            }
+        }
+    }
+
     pub fn responses(&self) -> TokenStream {
         let base = self
             .attribute
@@ -48,20 +359,131 @@ impl<'a> HttpErrorEnum<'a> {
             .and_then(|attribute| attribute.base.as_ref())
             .map(|r#type| quote!(<#r#type as ::utoipa::IntoResponses>::responses()));
 
-        let mut responses = base
-            .into_iter()
-            .chain(self.variants.iter().map(|variant| variant.responses()))
+        let variants = self
+            .variants
+            .iter()
+            .filter(|variant| variant.mask().is_none())
             .collect::<Vec<_>>();
 
-        if responses.is_empty() {
-            quote!(::std::collections::BTreeMap::default())
-        } else if responses.len() == 1 {
-            responses.remove(0)
+        if variants.iter().all(|variant| variant.cfgs.is_empty()) {
+            let mut responses = base
+                .into_iter()
+                .chain(variants.iter().map(|variant| variant.responses()))
+                .collect::<Vec<_>>();
+
+            if responses.is_empty() {
+                quote!(::std::collections::BTreeMap::default())
+            } else if responses.len() == 1 {
+                responses.remove(0)
+            } else {
+                quote! {
+                    ::breach::utoipa::merge_responses([
+                        #( #responses ),*
+                    ].into_iter())
+                }
+            }
+        } else {
+            // Statements, so `#[cfg]` of variants apply to their responses.
+            let base = base.map(|base| quote!(responses.push(#base);));
+            let pushes = variants.iter().map(|variant| {
+                let cfgs = &variant.cfgs;
+                let responses = variant.responses();
+
+                quote! {
+                    #( #cfgs )*
+                    responses.push(#responses);
+                }
+            });
+
+            quote!({
+                let mut responses = ::std::vec::Vec::new();
+                #base
+                #( #pushes )*
+                ::breach::utoipa::merge_responses(responses.into_iter())
+            })
+        }
+    }
+
+    pub fn server_fn_error(&self) -> TokenStream {
+        if let Some(variant) = self.variants.iter().find(|variant| variant.is_server_fn()) {
+            variant.server_fn_error()
         } else {
+            quote!(compile_error!("missing `#[http(server_fn)]` variant"))
+        }
+    }
+
+    pub fn mirror(&self) -> TokenStream {
+        let Some(tag) = &self.tag else {
+            return quote!(compile_error!(
+                "`mirror` requires an internally tagged enum (`#[serde(tag = ..)]`)"
+            ));
+        };
+        if !self.generics.params.is_empty() {
+            return quote!(compile_error!("`mirror` is not supported on generic enums"));
+        }
+
+        let ident = self.ident;
+        let vis = self.vis;
+        let mirror_ident = format_ident!("{}Response", ident);
+        let doc = format!("Client-side mirror of [`{ident}`].");
+
+        // Masked variants are never sent to clients.
+        let visible = || {
+            self.variants
+                .iter()
+                .filter(|variant| variant.mask().is_none())
+        };
+
+        let retryable = self.retryable();
+        let variants = visible().map(|variant| variant.mirror(retryable));
+        let candidates = visible().map(|variant| {
+            let cfgs = &variant.cfgs;
+            let candidate = variant.mirror_candidate(&mirror_ident);
+
             quote! {
-                ::breach::utoipa::merge_responses([
-                    #( #responses ),*
-                ].into_iter())
+                #( #cfgs )*
+                candidates.push(#candidate);
+            }
+        });
+        let fallbacks = visible()
+            .filter(|variant| !variant.is_delegating() && variant.field.is_some())
+            .map(|variant| {
+                let cfgs = &variant.cfgs;
+                let candidate = variant.mirror_candidate(&mirror_ident);
+
+                quote! {
+                    #( #cfgs )*
+                    fallbacks.push(#candidate);
+                }
+            });
+
+        quote! {
+            #[doc = #doc]
+            #[derive(Debug, ::breach::HttpError)]
+            #vis enum #mirror_ident {
+                #( #variants ),*
+            }
+
+            #[automatically_derived]
+            impl ::breach::client::HttpErrorMirror for #ident {
+                type Mirror = #mirror_ident;
+            }
+
+            #[automatically_derived]
+            impl<'de> ::breach::client::serde::Deserialize<'de> for #mirror_ident {
+                fn deserialize<D: ::breach::client::serde::Deserializer<'de>>(
+                    deserializer: D,
+                ) -> ::core::result::Result<Self, D::Error> {
+                    let mut candidates: ::std::vec::Vec<::breach::client::MirrorCandidate<Self>> =
+                        ::std::vec::Vec::new();
+                    #( #candidates )*
+
+                    let mut fallbacks: ::std::vec::Vec<::breach::client::MirrorCandidate<Self>> =
+                        ::std::vec::Vec::new();
+                    #( #fallbacks )*
+
+                    ::breach::client::deserialize_mirror(deserializer, #tag, &candidates, &fallbacks)
+                }
             }
         }
     }
@@ -78,19 +500,34 @@ impl<'a> HttpErrorEnum<'a> {
             }
         }
     }
+
+    fn retryable(&self) -> Option<&LitBool> {
+        self.attribute
+            .as_ref()
+            .and_then(|attribute| attribute.retryable.as_ref())
+    }
 }
 
 pub struct HttpErrorEnumVariant<'a> {
     enum_ident: &'a Ident,
     ident: &'a Ident,
+    attrs: &'a [Attribute],
     fields: &'a Fields,
     field: Option<&'a Field>,
+    code: String,
     attribute: Option<HttpErrorAttribute>,
+    exposure: Option<Exposure>,
+    cfgs: Vec<TokenStream>,
 }
 
 impl<'a> HttpErrorEnumVariant<'a> {
-    pub fn parse(enum_ident: &'a Ident, variant: &'a Variant) -> Result<Self> {
-        let field = match &variant.fields {
+    pub fn parse(
+        enum_ident: &'a Ident,
+        variant: &'a Variant,
+        rename_all: Option<RenameRule>,
+        expose: Option<&Exposure>,
+    ) -> Result<Self> {
+        let mut field = match &variant.fields {
             Fields::Named(fields) => {
                 return Err(Error::new(fields.span(), "named fields are not supported"));
             }
@@ -102,62 +539,443 @@ impl<'a> HttpErrorEnumVariant<'a> {
                     ));
                 }
 
-                fields.unnamed.first().filter(|&field| {
-                    !field.attrs.iter().any(|attribute| {
-                        if attribute.meta.path().is_ident("serde") {
-                            let mut skip = false;
+                match fields.unnamed.first() {
+                    Some(field) if !SerdeAttribute::parse_slice(&field.attrs)?.skip => Some(field),
+                    _ => None,
+                }
+            }
+            Fields::Unit => None,
+        };
 
-                            _ = attribute.parse_nested_meta(|meta| {
-                                if meta.path.is_ident("skip") {
-                                    skip = true;
-                                }
+        let attribute = HttpErrorAttribute::parse_slice(&variant.attrs)?;
 
-                                Ok(())
-                            });
+        let code = match attribute
+            .as_ref()
+            .and_then(|attribute| attribute.code.as_ref())
+        {
+            Some(code) => code.value(),
+            None => SerdeAttribute::parse_slice(&variant.attrs)?
+                .rename
+                .unwrap_or_else(|| {
+                    let ident = variant.ident.to_string();
+
+                    match rename_all {
+                        Some(rule) => rule.apply_to_variant(&ident),
+                        None => ident,
+                    }
+                }),
+        };
+
+        let is_delegating = attribute
+            .as_ref()
+            .is_none_or(|attribute| attribute.status.is_none());
+
+        if let Some(attribute) = attribute
+            .as_ref()
+            .filter(|attribute| !attribute.remap.is_empty())
+            && !is_delegating
+        {
+            return Err(Error::new(
+                attribute.remap[0].from.span(),
+                "`remap` is only supported on delegating variants, remove `status` to delegate to the inner error",
+            ));
+        }
+
+        let field_exposure = match field {
+            Some(field) => {
+                let field_attribute = HttpErrorFieldAttribute::parse_slice(&field.attrs)?;
+                if field_attribute.status {
+                    return Err(Error::new(
+                        field.span(),
+                        "`#[http(status)]` fields are only supported on structs",
+                    ));
+                }
 
-                            skip
-                        } else {
-                            false
-                        }
-                    })
-                })
+                field_attribute.expose
             }
-            Fields::Unit => None,
+            None => None,
+        };
+        let explicit_exposure = attribute
+            .as_ref()
+            .and_then(|attribute| attribute.expose.clone())
+            .or(field_exposure);
+
+        if let Some(field) = field
+            && let Some(attribute) = &attribute
+            && explicit_exposure.is_none()
+            && expose.is_none()
+        {
+            check_server_error_payload(
+                field.span(),
+                "variant",
+                attribute.status.as_ref(),
+                &attribute.statuses,
+            )?;
+        }
+
+        let exposure = match explicit_exposure {
+            Some(_) if is_delegating => {
+                return Err(Error::new(
+                    variant.ident.span(),
+                    "`expose` is not supported on delegating variants, use it on the inner error instead",
+                ));
+            }
+            Some(exposure) => Some(exposure),
+            None => expose.filter(|_| !is_delegating).cloned(),
         };
 
+        // Internal payloads are not part of the public shape of the error.
+        if exposure.as_ref().is_some_and(Exposure::is_internal) {
+            field = None;
+        }
+
         Ok(HttpErrorEnumVariant {
             enum_ident,
             ident: &variant.ident,
+            attrs: &variant.attrs,
             fields: &variant.fields,
             field,
-            attribute: HttpErrorAttribute::parse_slice(&variant.attrs)?,
+            code,
+            attribute,
+            exposure,
+            cfgs: cfgs(&variant.attrs)?,
         })
     }
 
+    pub fn is_server_fn(&self) -> bool {
+        self.attribute
+            .as_ref()
+            .is_some_and(|attribute| attribute.server_fn)
+    }
+
+    fn mask(&self) -> Option<&Ident> {
+        self.attribute
+            .as_ref()
+            .and_then(|attribute| attribute.mask.as_ref())
+    }
+
+    fn message(&self) -> Option<&Message> {
+        self.attribute
+            .as_ref()
+            .and_then(|attribute| attribute.message.as_ref())
+    }
+
+    fn status_attribute(&self) -> Option<&HttpErrorAttribute> {
+        self.attribute
+            .as_ref()
+            .filter(|attribute| attribute.status.is_some())
+    }
+
+    fn is_delegating(&self) -> bool {
+        self.status_attribute().is_none()
+    }
+
+    fn remap_with(
+        &self,
+        map: impl Fn(TokenStream) -> TokenStream,
+        fallback: TokenStream,
+    ) -> TokenStream {
+        match &self.attribute {
+            Some(attribute) => attribute.remap_with(quote!(value.status()), map, fallback),
+            None => fallback,
+        }
+    }
+
     pub fn status(&self) -> TokenStream {
-        self.arm(if let Some(attribute) = &self.attribute {
+        self.arm(if let Some(attribute) = self.status_attribute() {
             attribute.status()
         } else if self.field.is_some() {
-            quote!(value.status())
+            match &self.attribute {
+                Some(attribute) => attribute.remap_status(quote!(value.status())),
+                None => quote!(value.status()),
+            }
         } else {
             quote!(compile_error!("missing `#[http(status = ..)]` attribute"))
         })
     }
 
+    pub fn code(&self) -> TokenStream {
+        let code = &self.code;
+
+        self.arm(if self.is_delegating() && self.field.is_some() {
+            quote!(value.code().or(::core::option::Option::Some(#code)))
+        } else {
+            quote!(::core::option::Option::Some(#code))
+        })
+    }
+
+    pub fn message_key(&self) -> TokenStream {
+        let code = &self.code;
+
+        if let Some(message_key) = self
+            .attribute
+            .as_ref()
+            .and_then(|attribute| attribute.message_key.as_ref())
+        {
+            self.arm_with(false, quote!(::core::option::Option::Some(#message_key)))
+        } else if self.is_delegating() && self.field.is_some() {
+            self.arm(quote!(value.message_key().or(::core::option::Option::Some(#code))))
+        } else {
+            self.arm(quote!(::core::option::Option::Some(#code)))
+        }
+    }
+
+    pub fn message_keys(&self) -> TokenStream {
+        if let Some(message_key) = self
+            .attribute
+            .as_ref()
+            .and_then(|attribute| attribute.message_key.as_ref())
+        {
+            quote!([#message_key])
+        } else if let Some(field) = self.field.filter(|_| self.is_delegating()) {
+            let r#type = &field.ty;
+
+            quote!(<#r#type as ::breach::HttpError>::message_keys())
+        } else {
+            let code = &self.code;
+
+            quote!([#code])
+        }
+    }
+
+    pub fn jsonrpc_code(&self) -> TokenStream {
+        if let Some(attribute) = self
+            .attribute
+            .as_ref()
+            .filter(|attribute| attribute.jsonrpc.is_some())
+        {
+            self.arm_with(false, attribute.jsonrpc_code())
+        } else if self.is_delegating() && self.field.is_some() {
+            self.arm(quote!(::breach::jsonrpsee::JsonRpcError::jsonrpc_code(
+                value
+            )))
+        } else {
+            self.arm(quote!(::core::option::Option::None))
+        }
+    }
+
+    pub fn is_retryable(&self, default: Option<&LitBool>) -> TokenStream {
+        if let Some(retryable) = self.retryable(default) {
+            self.arm_with(false, quote!(#retryable))
+        } else if self.is_delegating() && self.field.is_some() {
+            self.arm(self.remap_with(
+                |status| quote!(::breach::is_retryable_status(#status)),
+                quote!(value.is_retryable()),
+            ))
+        } else {
+            let status = self
+                .status_attribute()
+                .map(|attribute| attribute.status())
+                .unwrap_or_else(|| {
+                    quote!(compile_error!("missing `#[http(status = ..)]` attribute"))
+                });
+
+            self.arm(quote!(::breach::is_retryable_status(#status)))
+        }
+    }
+
+    pub fn retry_after(&self) -> TokenStream {
+        self.arm(if self.is_delegating() && self.field.is_some() {
+            quote!(value.retry_after())
+        } else {
+            quote!(::core::option::Option::None)
+        })
+    }
+
+    pub fn reason(&self) -> TokenStream {
+        if let Some(reason) = self
+            .status_attribute()
+            .and_then(|attribute| attribute.reason.as_ref())
+        {
+            self.arm_with(false, quote!(::core::option::Option::Some(#reason)))
+        } else if self.is_delegating() && self.field.is_some() {
+            self.arm(self.remap_with(
+                |status| quote!(#status.canonical_reason()),
+                quote!(value.reason()),
+            ))
+        } else {
+            let status = self
+                .status_attribute()
+                .map(|attribute| attribute.status())
+                .unwrap_or_else(|| {
+                    quote!(compile_error!("missing `#[http(status = ..)]` attribute"))
+                });
+
+            self.arm_with(false, quote!(#status.canonical_reason()))
+        }
+    }
+
+    pub fn level(&self, default: Option<&Level>) -> TokenStream {
+        if let Some(level) = self
+            .attribute
+            .as_ref()
+            .and_then(|attribute| attribute.level.as_ref())
+            .or_else(|| default.filter(|_| !self.is_delegating()))
+        {
+            self.arm_with(false, quote!(#level))
+        } else if self.is_delegating() && self.field.is_some() {
+            self.arm(self.remap_with(
+                |status| quote!(::breach::Level::from_status(#status)),
+                quote!(value.level()),
+            ))
+        } else {
+            let status = self
+                .status_attribute()
+                .map(|attribute| attribute.status())
+                .unwrap_or_else(|| {
+                    quote!(compile_error!("missing `#[http(status = ..)]` attribute"))
+                });
+
+            self.arm(quote!(::breach::Level::from_status(#status)))
+        }
+    }
+
+    pub fn masked(&self, target: Option<&HttpErrorEnumVariant>) -> TokenStream {
+        if let Some(target) = target {
+            let ident = target.ident;
+
+            match (target.fields, self.fields) {
+                (Fields::Unnamed(_), Fields::Unnamed(_)) => self.arm_with(
+                    true,
+                    quote!(::core::option::Option::Some(Self::#ident(
+                        ::core::convert::From::from(value)
+                    ))),
+                ),
+                (Fields::Unnamed(_), _) => self.arm_with(
+                    false,
+                    quote!(::core::option::Option::Some(Self::#ident(
+                        ::core::default::Default::default()
+                    ))),
+                ),
+                _ => self.arm_with(false, quote!(::core::option::Option::Some(Self::#ident))),
+            }
+        } else if self.is_delegating() && self.field.is_some() {
+            let ident = self.ident;
+
+            self.arm(quote!(value.masked().map(Self::#ident)))
+        } else {
+            self.arm(quote!(::core::option::Option::None))
+        }
+    }
+
+    pub fn exposure(&self) -> TokenStream {
+        if let Some(exposure) = &self.exposure {
+            self.arm_with(false, quote!(#exposure))
+        } else if self.is_delegating() && self.field.is_some() {
+            self.arm(quote!(value.exposure()))
+        } else {
+            self.arm_with(false, quote!(::breach::Exposure::Public))
+        }
+    }
+
+    pub fn internal(&self) -> TokenStream {
+        if self.is_delegating() && self.field.is_some() {
+            self.arm(quote!(value.internal()))
+        } else if matches!(self.fields, Fields::Unnamed(_)) && self.field.is_none() {
+            // Skipped payloads are internal errors, if they are (or dereference to) `std::error::Error`.
+            self.arm_with(true, internal_error(quote!(value)))
+        } else {
+            self.arm(quote!(::core::option::Option::None))
+        }
+    }
+
+    pub fn display(&self, default: Option<&Message>) -> TokenStream {
+        if let Some(message) = self
+            .message()
+            .or_else(|| default.filter(|_| !self.is_delegating()))
+        {
+            // Arguments refer to the payload (`{0}`) or fields of the payload (`{id}`).
+            let has_payload = matches!(self.fields, Fields::Unnamed(_));
+            let write = message.write(|member| match member {
+                Member::Unnamed(index) if has_payload && index.index == 0 => quote!(value),
+                Member::Named(ident) if has_payload => quote!(value.#ident),
+                _ => quote!(compile_error!("unknown message argument")),
+            });
+
+            self.arm_with(has_payload && message.has_arguments(), write)
+        } else if self.is_delegating() && self.field.is_some() {
+            self.arm(quote!(::core::fmt::Display::fmt(value, f)))
+        } else {
+            self.arm_with(
+                false,
+                quote!(compile_error!("missing `#[http(message = ..)]` attribute")),
+            )
+        }
+    }
+
+    pub fn source(&self) -> TokenStream {
+        if self.is_delegating() && self.field.is_some() && self.message().is_none() {
+            // The message is forwarded to the nested error, so is its source, as it would repeat the message otherwise.
+            self.arm(quote!(::std::error::Error::source(value)))
+        } else if matches!(self.fields, Fields::Unnamed(_)) {
+            self.arm_with(true, internal_error(quote!(value)))
+        } else {
+            self.arm_with(false, quote!(::core::option::Option::None))
+        }
+    }
+
+    pub fn is_reported(&self, default: Option<&LitBool>) -> TokenStream {
+        if let Some(report) = self
+            .attribute
+            .as_ref()
+            .and_then(|attribute| attribute.report.as_ref())
+            .or_else(|| default.filter(|_| !self.is_delegating()))
+        {
+            self.arm_with(false, quote!(#report))
+        } else if self.is_delegating() && self.field.is_some() {
+            self.arm(self.remap_with(
+                |status| quote!(#status.is_server_error()),
+                quote!(value.is_reported()),
+            ))
+        } else {
+            let status = self
+                .status_attribute()
+                .map(|attribute| attribute.status())
+                .unwrap_or_else(|| {
+                    quote!(compile_error!("missing `#[http(status = ..)]` attribute"))
+                });
+
+            self.arm(quote!(#status.is_server_error()))
+        }
+    }
+
+    pub fn path(&self) -> TokenStream {
+        let enum_ident = self.enum_ident.to_string();
+        let ident = self.ident.to_string();
+
+        self.arm(if self.is_delegating() && self.field.is_some() {
+            quote!({
+                let mut path = ::std::vec![#enum_ident, #ident];
+                path.extend(value.path());
+                path
+            })
+        } else {
+            quote!(::std::vec![#enum_ident, #ident])
+        })
+    }
+
     pub fn responses(&self) -> TokenStream {
-        if let Some(attribute) = &self.attribute {
-            attribute.responses(self.field.as_ref().map(|field| field.ty.to_token_stream()))
+        if let Some(attribute) = self.status_attribute() {
+            attribute.responses(
+                self.field
+                    .as_ref()
+                    .map(|field| pointee(&field.ty).to_token_stream()),
+            )
         } else if let Some(field) = &self.field {
-            let r#type = &field.ty;
+            let r#type = pointee(&field.ty);
+            let responses = quote!(<#r#type as ::utoipa::IntoResponses>::responses());
 
-            quote!(<#r#type as ::utoipa::IntoResponses>::responses())
+            match &self.attribute {
+                Some(attribute) => attribute.remap_responses(responses),
+                None => responses,
+            }
         } else {
             quote!(compile_error!("missing `#[http(status = ..)]` attribute"))
         }
     }
 
     pub fn hook(&self) -> TokenStream {
-        self.arm(if self.attribute.is_none() && self.field.is_some() {
+        self.arm(if self.is_delegating() && self.field.is_some() {
             quote!({
                 value.hook();
             })
@@ -166,32 +984,173 @@ impl<'a> HttpErrorEnumVariant<'a> {
         })
     }
 
+    pub fn mirror(&self, default: Option<&LitBool>) -> TokenStream {
+        let ident = self.ident;
+        let code = &self.code;
+        let retryable = self
+            .retryable(default)
+            .map(|retryable| quote!(, retryable = #retryable));
+        let docs = self
+            .attrs
+            .iter()
+            .filter(|attribute| attribute.path().is_ident("doc"));
+        let cfgs = &self.cfgs;
+
+        if let Some(status) = self
+            .status_attribute()
+            .and_then(|attribute| attribute.status.as_ref())
+        {
+            let field = self.field.map(|field| {
+                let r#type = &field.ty;
+
+                quote!((#r#type))
+            });
+
+            let expose = self.field.map(|_| quote!(, expose));
+            let reason = self
+                .status_attribute()
+                .and_then(|attribute| attribute.reason.as_ref())
+                .map(|reason| quote!(, reason = #reason));
+
+            quote! {
+                #( #cfgs )*
+                #( #docs )*
+                #[http(status = #status, code = #code #retryable #reason #expose)]
+                #ident #field
+            }
+        } else {
+            // The variants of the nested error are not known here, so its mirror is nested instead of flattened.
+            let field = self.field.map(|field| {
+                let r#type = &field.ty;
+
+                quote!((<#r#type as ::breach::client::HttpErrorMirror>::Mirror))
+            });
+
+            let remap = self
+                .attribute
+                .as_ref()
+                .filter(|attribute| !attribute.remap.is_empty())
+                .map(|attribute| {
+                    let pairs = attribute.remap.iter().map(|remap| {
+                        let from = &remap.from;
+                        let to = &remap.to;
+
+                        quote!(#from => #to)
+                    });
+
+                    quote!(, remap( #( #pairs ),* ))
+                });
+
+            quote! {
+                #( #cfgs )*
+                #( #docs )*
+                #[http(code = #code #retryable #remap)]
+                #ident #field
+            }
+        }
+    }
+
+    pub fn mirror_candidate(&self, mirror_ident: &Ident) -> TokenStream {
+        let ident = self.ident;
+
+        if self.field.is_some() {
+            quote! {
+                |value| ::breach::client::serde::Deserialize::deserialize(value)
+                    .ok()
+                    .map(#mirror_ident::#ident)
+            }
+        } else {
+            quote!(|_| ::core::option::Option::Some(#mirror_ident::#ident))
+        }
+    }
+
+    pub fn server_fn_error(&self) -> TokenStream {
+        let enum_ident = self.enum_ident;
+        let ident = self.ident;
+
+        match self.fields {
+            Fields::Named(_) => unreachable!("named fields are not supported"),
+            Fields::Unnamed(_) => {
+                quote!(#enum_ident::#ident(::core::convert::From::from(value)))
+            }
+            Fields::Unit => quote!({
+                _ = value;
+
+                #enum_ident::#ident
+            }),
+        }
+    }
+
+    fn retryable<'b>(&'b self, default: Option<&'b LitBool>) -> Option<&'b LitBool> {
+        self.attribute
+            .as_ref()
+            .and_then(|attribute| attribute.retryable.as_ref())
+            .or_else(|| default.filter(|_| !self.is_delegating()))
+    }
+
     fn arm(&self, tokens: TokenStream) -> TokenStream {
+        self.arm_with(self.is_delegating(), tokens)
+    }
+
+    fn arm_with(&self, bind: bool, tokens: TokenStream) -> TokenStream {
         let enum_ident = self.enum_ident;
         let ident = self.ident;
+        let cfgs = &self.cfgs;
 
         match self.fields {
             Fields::Named(_) => {
                 quote! {
+                    #( #cfgs )*
                     #enum_ident::#ident { .. } => #tokens
                 }
             }
             Fields::Unnamed(fields) => {
-                let idents: Vec<TokenStream> = if self.attribute.is_some() {
-                    fields.unnamed.iter().map(|_| quote!(_)).collect()
-                } else {
+                let idents: Vec<TokenStream> = if bind {
                     fields.unnamed.iter().map(|_| quote!(value)).collect()
+                } else {
+                    fields.unnamed.iter().map(|_| quote!(_)).collect()
                 };
 
                 quote! {
+                    #( #cfgs )*
                     #enum_ident::#ident( #(#idents),* ) => #tokens
                 }
             }
             Fields::Unit => {
                 quote! {
+                    #( #cfgs )*
                     #enum_ident::#ident => #tokens
                 }
             }
         }
     }
 }
+
+// `#[cfg]` and `#[cfg_attr(.., cfg(..))]` attributes of a variant, to apply to the code generated for it.
+fn cfgs(attrs: &[Attribute]) -> Result<Vec<TokenStream>> {
+    let mut cfgs = Vec::new();
+
+    for attribute in attrs {
+        if attribute.path().is_ident("cfg") {
+            cfgs.push(attribute.to_token_stream());
+        } else if attribute.path().is_ident("cfg_attr") {
+            let metas =
+                attribute.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+            let mut metas = metas.into_iter();
+
+            let Some(predicate) = metas.next() else {
+                continue;
+            };
+            // Other attributes (e.g. `serde` or `http`) are not valid on the generated code.
+            let metas = metas
+                .filter(|meta| meta.path().is_ident("cfg"))
+                .collect::<Vec<_>>();
+
+            if !metas.is_empty() {
+                cfgs.push(quote!(#[cfg_attr(#predicate, #( #metas ),*)]));
+            }
+        }
+    }
+
+    Ok(cfgs)
+}
@@ -0,0 +1,29 @@
+use syn::{GenericArgument, PathArguments, Type};
+
+// Type behind `Box`, `Arc` and references, for traits which can't be implemented for these (e.g. `IntoResponses`).
+pub fn pointee(r#type: &Type) -> &Type {
+    match r#type {
+        Type::Group(group) => pointee(&group.elem),
+        Type::Paren(paren) => pointee(&paren.elem),
+        Type::Reference(reference) => pointee(&reference.elem),
+        Type::Path(path) if path.qself.is_none() => {
+            let Some(segment) = path.path.segments.last() else {
+                return r#type;
+            };
+            if segment.ident != "Box" && segment.ident != "Arc" {
+                return r#type;
+            }
+
+            match &segment.arguments {
+                PathArguments::AngleBracketed(arguments) if arguments.args.len() == 1 => {
+                    match arguments.args.first() {
+                        Some(GenericArgument::Type(r#type)) => pointee(r#type),
+                        _ => r#type,
+                    }
+                }
+                _ => r#type,
+            }
+        }
+        _ => r#type,
+    }
+}
@@ -1,35 +1,390 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{DataStruct, DeriveInput, Error, Result, spanned::Spanned};
+use syn::{
+    DataStruct, DeriveInput, Error, Generics, Ident, Index, Member, Result, Type, parse_quote,
+    spanned::Spanned,
+};
 
-use crate::http::attribute::HttpErrorDataAttribute;
+use crate::{
+    http::{
+        attribute::{
+            HttpErrorDataAttribute, HttpErrorFieldAttribute, check_server_error_payload,
+            internal_error,
+        },
+        pointer::pointee,
+    },
+    serde::SerdeAttribute,
+    status::Status,
+};
 
-pub struct HttpErrorStruct {
+pub struct HttpErrorStruct<'a> {
+    ident: &'a Ident,
+    generics: &'a Generics,
     attribute: HttpErrorDataAttribute,
+    transparent: Option<(Member, &'a Type)>,
+    context: Vec<Member>,
+    source: Option<Member>,
 }
 
-impl<'a> HttpErrorStruct {
-    pub fn parse(input: &'a DeriveInput, _data: &'a DataStruct) -> Result<Self> {
-        let Some(attribute) = HttpErrorDataAttribute::parse_slice(&input.attrs)? else {
+impl<'a> HttpErrorStruct<'a> {
+    pub fn parse(input: &'a DeriveInput, data: &'a DataStruct) -> Result<Self> {
+        let Some(mut attribute) = HttpErrorDataAttribute::parse_slice(&input.attrs)? else {
             return Err(Error::new(input.span(), "missing http attribute"));
         };
 
-        Ok(HttpErrorStruct { attribute })
+        let mut source = None;
+
+        for (index, field) in data.fields.iter().enumerate() {
+            let field_attribute = HttpErrorFieldAttribute::parse_slice(&field.attrs)?;
+
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(Index::from(index)),
+            };
+
+            // Exposure applies to the whole payload, a single field can't be hidden from its serialization.
+            if field_attribute.expose.is_some() {
+                return Err(Error::new(
+                    field.span(),
+                    "`expose` is not supported on struct fields, \
+                    use `#[http(expose = ..)]` on the struct or `#[serde(skip)]` on the field instead",
+                ));
+            }
+
+            if field_attribute.source {
+                if source.is_some() {
+                    return Err(Error::new(
+                        field.span(),
+                        "only a single `#[http(source)]` field is allowed",
+                    ));
+                }
+
+                source = Some(member.clone());
+            }
+
+            if !field_attribute.status {
+                continue;
+            }
+
+            if attribute.status.is_some() {
+                return Err(Error::new(
+                    field.span(),
+                    "conflicting `#[http(status)]` field and `#[http(status = ..)]` attribute",
+                ));
+            }
+
+            attribute.status = Some(Status::expr(parse_quote!(self.#member)));
+        }
+
+        if attribute.expose.is_none() && !attribute.transparent {
+            let payload = data
+                .fields
+                .iter()
+                .map(|field| Ok((field, SerdeAttribute::parse_slice(&field.attrs)?.skip)))
+                .collect::<Result<Vec<_>>>()?
+                .into_iter()
+                .find_map(|(field, skip)| (!skip).then_some(field));
+
+            if let Some(field) = payload {
+                check_server_error_payload(
+                    field.span(),
+                    "struct",
+                    attribute.status.as_ref(),
+                    &attribute.statuses,
+                )?;
+            }
+        }
+
+        // Like `thiserror`, a field named `source` is the source of the error by default.
+        let source = source.or_else(|| {
+            data.fields
+                .iter()
+                .filter_map(|field| field.ident.as_ref())
+                .find(|ident| *ident == "source")
+                .map(|ident| Member::Named(ident.clone()))
+        });
+
+        let mut context = Vec::new();
+        let transparent = if attribute.transparent {
+            // Serialization is delegated by serde, the derive only checks that it is.
+            if !SerdeAttribute::parse_slice(&input.attrs)?.transparent {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "`transparent` requires `#[serde(transparent)]` to serialize the inner error",
+                ));
+            }
+
+            // Like `#[serde(transparent)]`, other fields (e.g. context) must be skipped.
+            let mut inner = None;
+            for (index, field) in data.fields.iter().enumerate() {
+                let member = match &field.ident {
+                    Some(ident) => Member::Named(ident.clone()),
+                    None => Member::Unnamed(Index::from(index)),
+                };
+
+                if SerdeAttribute::parse_slice(&field.attrs)?.skip {
+                    context.push(member);
+                } else if inner.is_none() {
+                    inner = Some((member, &field.ty));
+                } else {
+                    return Err(Error::new(
+                        field.span(),
+                        "`transparent` requires a single field which is not skipped",
+                    ));
+                }
+            }
+
+            let Some(inner) = inner else {
+                return Err(Error::new(
+                    data.fields.span(),
+                    "`transparent` requires a single field which is not skipped",
+                ));
+            };
+            if let Some(status) = &attribute.status {
+                return Err(Error::new(
+                    status.span(),
+                    "`transparent` delegates the status to the inner error",
+                ));
+            }
+
+            Some(inner)
+        } else {
+            None
+        };
+
+        Ok(HttpErrorStruct {
+            ident: &input.ident,
+            generics: &input.generics,
+            attribute,
+            transparent,
+            context,
+            source,
+        })
     }
 
     pub fn attribute(&self) -> Option<&HttpErrorDataAttribute> {
         Some(&self.attribute)
     }
 
+    pub fn delegates(&self) -> Vec<Type> {
+        match &self.transparent {
+            Some((_, r#type)) => vec![(*r#type).clone()],
+            None => Vec::new(),
+        }
+    }
+
+    pub fn payloads(&self) -> Vec<Type> {
+        match &self.transparent {
+            Some(_) => Vec::new(),
+            None => vec![parse_quote!(Self)],
+        }
+    }
+
     pub fn status(&self) -> TokenStream {
-        self.attribute.status()
+        match &self.transparent {
+            Some((member, _)) => quote!(self.#member.status()),
+            None => self.attribute.status(),
+        }
+    }
+
+    pub fn code(&self) -> TokenStream {
+        match &self.transparent {
+            Some((member, _)) if self.attribute.code.is_none() => quote!(self.#member.code()),
+            _ => self.attribute.code(),
+        }
+    }
+
+    pub fn message_key(&self) -> TokenStream {
+        match (&self.transparent, &self.attribute.message_key) {
+            (_, Some(message_key)) => quote!(::core::option::Option::Some(#message_key)),
+            (Some((member, _)), None) => quote!(self.#member.message_key()),
+            (None, None) => quote!(self.code()),
+        }
+    }
+
+    pub fn message_keys(&self) -> TokenStream {
+        match (
+            &self.transparent,
+            self.attribute
+                .message_key
+                .as_ref()
+                .or(self.attribute.code.as_ref()),
+        ) {
+            (_, Some(message_key)) => quote!(::std::vec![#message_key]),
+            (Some((_, r#type)), None) => quote!(<#r#type as ::breach::HttpError>::message_keys()),
+            (None, None) => quote!(::std::vec::Vec::new()),
+        }
+    }
+
+    pub fn jsonrpc_code(&self) -> TokenStream {
+        match &self.transparent {
+            Some((member, _)) if self.attribute.jsonrpc.is_none() => {
+                quote!(::breach::jsonrpsee::JsonRpcError::jsonrpc_code(&self.#member))
+            }
+            _ => self.attribute.jsonrpc_code(),
+        }
+    }
+
+    pub fn is_retryable(&self) -> TokenStream {
+        match &self.transparent {
+            Some((member, _)) if self.attribute.retryable.is_none() => {
+                quote!(self.#member.is_retryable())
+            }
+            _ => self.attribute.is_retryable(),
+        }
+    }
+
+    pub fn retry_after(&self) -> TokenStream {
+        match &self.transparent {
+            Some((member, _)) if self.attribute.retry_after.is_none() => {
+                quote!(self.#member.retry_after())
+            }
+            _ => self.attribute.retry_after(),
+        }
+    }
+
+    pub fn reason(&self) -> TokenStream {
+        match (&self.attribute.reason, &self.transparent) {
+            (Some(reason), _) => quote!(::core::option::Option::Some(#reason)),
+            (None, Some((member, _))) => quote!(self.#member.reason()),
+            (None, None) => quote!(self.status().canonical_reason()),
+        }
+    }
+
+    pub fn level(&self) -> TokenStream {
+        match &self.transparent {
+            Some((member, _)) if self.attribute.level.is_none() => quote!(self.#member.level()),
+            _ => self.attribute.level(),
+        }
+    }
+
+    pub fn masked(&self) -> TokenStream {
+        match &self.transparent {
+            Some((member, _)) => {
+                let context = &self.context;
+
+                // Context fields which don't implement `Clone` can't be rebuilt, so the error is not masked.
+                quote! {
+                    self.#member.masked().and_then(|masked| {
+                        use ::breach::__private::{ContextClone as _, ContextCloneNone as _};
+
+                        ::core::option::Option::Some(Self {
+                            #member: ::core::convert::From::from(masked),
+                            #( #context: (&::breach::__private::Context(&self.#context)).context_clone()?, )*
+                        })
+                    })
+                }
+            }
+            None => quote!(::core::option::Option::None),
+        }
+    }
+
+    pub fn exposure(&self) -> TokenStream {
+        match &self.transparent {
+            Some((member, _)) if self.attribute.expose.is_none() => {
+                quote!(self.#member.exposure())
+            }
+            _ => self.attribute.exposure(),
+        }
+    }
+
+    pub fn exposed(&self) -> Option<TokenStream> {
+        self.attribute
+            .expose
+            .is_some()
+            .then(|| quote!(::breach::Exposed::new(error, ::core::option::Option::None)))
+    }
+
+    pub fn internal(&self) -> TokenStream {
+        match &self.transparent {
+            Some((member, _)) => quote!(self.#member.internal()),
+            None => quote!(::core::option::Option::None),
+        }
+    }
+
+    pub fn display(&self) -> Option<TokenStream> {
+        self.attribute
+            .message
+            .as_ref()
+            .map(|message| message.write(|member| quote!(self.#member)))
+    }
+
+    pub fn source(&self) -> TokenStream {
+        match self
+            .transparent
+            .as_ref()
+            .map(|(member, _)| member)
+            .or(self.source.as_ref())
+        {
+            Some(member) => internal_error(quote!(&self.#member)),
+            None => quote!(::core::option::Option::None),
+        }
+    }
+
+    pub fn is_reported(&self) -> TokenStream {
+        match &self.transparent {
+            Some((member, _)) if self.attribute.report.is_none() => {
+                quote!(self.#member.is_reported())
+            }
+            _ => self.attribute.is_reported(),
+        }
+    }
+
+    pub fn path(&self) -> TokenStream {
+        let ident = self.ident.to_string();
+
+        match &self.transparent {
+            Some((member, _)) => quote!({
+                let mut path = ::std::vec![#ident];
+                path.extend(self.#member.path());
+                path
+            }),
+            None => quote!(::std::vec![#ident]),
+        }
     }
 
     pub fn responses(&self) -> TokenStream {
-        self.attribute.responses(Some(quote!(Self)))
+        match &self.transparent {
+            Some((_, r#type)) => {
+                let r#type = pointee(r#type);
+
+                quote!(<#r#type as ::utoipa::IntoResponses>::responses())
+            }
+            None => self.attribute.responses(Some(quote!(Self))),
+        }
+    }
+
+    pub fn server_fn_error(&self) -> TokenStream {
+        quote!(compile_error!("`leptos` is only supported on enums"))
+    }
+
+    pub fn mirror(&self) -> TokenStream {
+        let ident = self.ident;
+        let (impl_generics, type_generics, where_clause) = self.generics.split_for_impl();
+
+        let mirror = match &self.transparent {
+            Some((_, r#type)) => quote!(<#r#type as ::breach::client::HttpErrorMirror>::Mirror),
+            None => quote!(Self),
+        };
+
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::breach::client::HttpErrorMirror for #ident #type_generics #where_clause {
+                type Mirror = #mirror;
+            }
+        }
     }
 
     pub fn hook(&self) -> TokenStream {
-        self.attribute.hook()
+        let hook = self.attribute.hook();
+
+        match &self.transparent {
+            Some((member, _)) => quote! {
+                #hook
+
+                self.#member.hook();
+            },
+            None => hook,
+        }
     }
 }
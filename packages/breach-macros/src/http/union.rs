@@ -1,5 +1,5 @@
 use proc_macro2::TokenStream;
-use syn::{DataUnion, DeriveInput, Result};
+use syn::{DataUnion, DeriveInput, Result, Type};
 
 use crate::http::attribute::HttpErrorDataAttribute;
 
@@ -14,14 +14,94 @@ impl HttpErrorUnion {
         None
     }
 
+    pub fn delegates(&self) -> Vec<Type> {
+        todo!()
+    }
+
+    pub fn payloads(&self) -> Vec<Type> {
+        todo!()
+    }
+
     pub fn status(&self) -> TokenStream {
         todo!()
     }
 
+    pub fn code(&self) -> TokenStream {
+        todo!()
+    }
+
+    pub fn message_key(&self) -> TokenStream {
+        todo!()
+    }
+
+    pub fn message_keys(&self) -> TokenStream {
+        todo!()
+    }
+
+    pub fn jsonrpc_code(&self) -> TokenStream {
+        todo!()
+    }
+
+    pub fn is_retryable(&self) -> TokenStream {
+        todo!()
+    }
+
+    pub fn retry_after(&self) -> TokenStream {
+        todo!()
+    }
+
+    pub fn reason(&self) -> TokenStream {
+        todo!()
+    }
+
+    pub fn level(&self) -> TokenStream {
+        todo!()
+    }
+
+    pub fn masked(&self) -> TokenStream {
+        todo!()
+    }
+
+    pub fn exposure(&self) -> TokenStream {
+        todo!()
+    }
+
+    pub fn exposed(&self) -> Option<TokenStream> {
+        todo!()
+    }
+
+    pub fn internal(&self) -> TokenStream {
+        todo!()
+    }
+
+    pub fn display(&self) -> Option<TokenStream> {
+        todo!()
+    }
+
+    pub fn source(&self) -> TokenStream {
+        todo!()
+    }
+
+    pub fn is_reported(&self) -> TokenStream {
+        todo!()
+    }
+
+    pub fn path(&self) -> TokenStream {
+        todo!()
+    }
+
     pub fn responses(&self) -> TokenStream {
         todo!()
     }
 
+    pub fn server_fn_error(&self) -> TokenStream {
+        todo!()
+    }
+
+    pub fn mirror(&self) -> TokenStream {
+        todo!()
+    }
+
     pub fn hook(&self) -> TokenStream {
         todo!()
     }
@@ -0,0 +1,43 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, quote};
+use syn::{
+    Error, Ident, Result,
+    parse::{Parse, ParseStream},
+};
+
+#[derive(Clone)]
+pub struct Level {
+    ident: Ident,
+}
+
+impl ToTokens for Level {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let ident = &self.ident;
+
+        tokens.extend(quote!(::breach::Level::#ident));
+    }
+}
+
+impl Parse for Level {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident: Ident = input.parse()?;
+
+        let variant = match ident.to_string().as_str() {
+            "error" => "Error",
+            "warn" => "Warn",
+            "info" => "Info",
+            "debug" => "Debug",
+            "trace" => "Trace",
+            _ => {
+                return Err(Error::new(
+                    ident.span(),
+                    "invalid level, expected one of `error`, `warn`, `info`, `debug` or `trace`",
+                ));
+            }
+        };
+
+        Ok(Self {
+            ident: Ident::new(variant, ident.span().resolved_at(Span::call_site())),
+        })
+    }
+}
@@ -2,7 +2,11 @@
 
 //! Breach macros.
 
+mod exposure;
 mod http;
+mod level;
+mod message;
+mod serde;
 mod status;
 
 use proc_macro::TokenStream;
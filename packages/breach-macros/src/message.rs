@@ -0,0 +1,94 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    Ident, Index, LitStr, Member, Result,
+    parse::{Parse, ParseStream},
+};
+
+pub struct Message {
+    format: LitStr,
+    arguments: Vec<(Ident, Member)>,
+}
+
+impl Message {
+    pub fn has_arguments(&self) -> bool {
+        !self.arguments.is_empty()
+    }
+
+    // `write!` of the message, with `{field}` and `{0}` arguments resolved to expressions by `member`.
+    pub fn write(&self, member: impl Fn(&Member) -> TokenStream) -> TokenStream {
+        let format = &self.format;
+        let arguments = self.arguments.iter().map(|(ident, field)| {
+            let expr = member(field);
+
+            quote!(#ident = #expr)
+        });
+
+        quote!(::core::write!(f, #format #( , #arguments )*))
+    }
+}
+
+impl Parse for Message {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lit: LitStr = input.parse()?;
+        let value = lit.value();
+
+        let mut format = String::with_capacity(value.len());
+        let mut arguments: Vec<(Ident, Member)> = Vec::new();
+
+        let mut chars = value.chars().peekable();
+        while let Some(char) = chars.next() {
+            format.push(char);
+
+            if char != '{' {
+                continue;
+            }
+            if chars.peek() == Some(&'{') {
+                format.extend(chars.next());
+                continue;
+            }
+
+            let mut placeholder = String::new();
+            while let Some(char) = chars.next_if(|char| *char != '}') {
+                placeholder.push(char);
+            }
+
+            let (argument, spec) = match placeholder.find(':') {
+                Some(index) => placeholder.split_at(index),
+                None => (placeholder.as_str(), ""),
+            };
+
+            // Positional arguments are renamed, as `write!` has no positional arguments to refer to.
+            let resolved = if let Ok(index) = argument.parse::<u32>() {
+                Some((
+                    format_ident!("_{}", index),
+                    Member::Unnamed(Index {
+                        index,
+                        span: lit.span(),
+                    }),
+                ))
+            } else {
+                syn::parse_str::<Ident>(argument)
+                    .ok()
+                    .map(|ident| (ident.clone(), Member::Named(ident)))
+            };
+
+            match resolved {
+                Some((ident, member)) => {
+                    format.push_str(&ident.to_string());
+                    format.push_str(spec);
+
+                    if !arguments.iter().any(|(existing, _)| *existing == ident) {
+                        arguments.push((ident, member));
+                    }
+                }
+                None => format.push_str(&placeholder),
+            }
+        }
+
+        Ok(Self {
+            format: LitStr::new(&format, lit.span()),
+            arguments,
+        })
+    }
+}
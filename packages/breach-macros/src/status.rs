@@ -1,19 +1,27 @@
 use http::StatusCode;
-use proc_macro2::Span;
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
 use syn::{
-    Error, Ident, LitInt, Result,
+    Error, Expr, ExprLit, Ident, Lit, LitInt, Result,
     parse::{Parse, ParseStream},
 };
 
 #[derive(Clone)]
 pub struct Status {
-    pub code: StatusCode,
+    pub code: Option<StatusCode>,
     raw: RawStatusCode,
 }
 
 impl Status {
-    fn as_text(&self) -> &'static str {
-        match self.code {
+    pub fn expr(expr: Expr) -> Self {
+        Self {
+            code: None,
+            raw: RawStatusCode::Expr(Box::new(expr)),
+        }
+    }
+
+    fn as_text(&self) -> Option<&'static str> {
+        Some(match self.code? {
             StatusCode::CONTINUE => "CONTINUE",
             StatusCode::SWITCHING_PROTOCOLS => "SWITCHING_PROTOCOLS",
             StatusCode::PROCESSING => "PROCESSING",
@@ -76,14 +84,39 @@ impl Status {
             StatusCode::LOOP_DETECTED => "LOOP_DETECTED",
             StatusCode::NOT_EXTENDED => "NOT_EXTENDED",
             StatusCode::NETWORK_AUTHENTICATION_REQUIRED => "NETWORK_AUTHENTICATION_REQUIRED",
-            _ => unimplemented!("unknown HTTP status code"),
-        }
+            _ => return None,
+        })
     }
 
-    pub fn as_ident(&self) -> Ident {
-        match &self.raw {
+    pub fn as_status_code(&self) -> TokenStream {
+        let ident = match &self.raw {
             RawStatusCode::Ident(ident) => ident.clone(),
-            RawStatusCode::Lit(_) => Ident::new(self.as_text(), Span::call_site()),
+            RawStatusCode::Lit(lit) => match (self.as_text(), self.code) {
+                (Some(text), _) => Ident::new(text, lit.span()),
+                (None, code) => {
+                    // Non-standard status codes have no associated constant, but are validated by `parse`.
+                    let code = code
+                        .expect("literal status code should be validated")
+                        .as_u16();
+
+                    return quote! {
+                        ::breach::http::StatusCode::from_u16(#code).expect("valid HTTP status code")
+                    };
+                }
+            },
+            RawStatusCode::Expr(expr) => return quote!((#expr)),
+        };
+
+        quote!(::breach::http::StatusCode::#ident)
+    }
+}
+
+impl ToTokens for Status {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match &self.raw {
+            RawStatusCode::Ident(ident) => ident.to_tokens(tokens),
+            RawStatusCode::Lit(lit) => lit.to_tokens(tokens),
+            RawStatusCode::Expr(expr) => expr.to_tokens(tokens),
         }
     }
 }
@@ -91,7 +124,7 @@ impl Status {
 impl Parse for Status {
     fn parse(input: ParseStream) -> Result<Self> {
         let raw: RawStatusCode = input.parse()?;
-        let code: StatusCode = (&raw).try_into()?;
+        let code: Option<StatusCode> = (&raw).try_into()?;
 
         Ok(Self { raw, code })
     }
@@ -101,93 +134,137 @@ impl Parse for Status {
 pub enum RawStatusCode {
     Ident(Ident),
     Lit(LitInt),
+    Expr(Box<Expr>),
 }
 
-impl TryFrom<&RawStatusCode> for StatusCode {
+impl TryFrom<&RawStatusCode> for Option<StatusCode> {
     type Error = Error;
 
     fn try_from(value: &RawStatusCode) -> std::result::Result<Self, Self::Error> {
         match value {
-            RawStatusCode::Ident(ident) => Ok(match ident.to_string().as_str() {
-                "CONTINUE" => StatusCode::CONTINUE,
-                "SWITCHING_PROTOCOLS" => StatusCode::SWITCHING_PROTOCOLS,
-                "PROCESSING" => StatusCode::PROCESSING,
-                "EARLY_HINTS" => StatusCode::EARLY_HINTS,
-                "OK" => StatusCode::OK,
-                "CREATED" => StatusCode::CREATED,
-                "ACCEPTED" => StatusCode::ACCEPTED,
-                "NON_AUTHORITATIVE_INFORMATION" => StatusCode::NON_AUTHORITATIVE_INFORMATION,
-                "NO_CONTENT" => StatusCode::NO_CONTENT,
-                "RESET_CONTENT" => StatusCode::RESET_CONTENT,
-                "PARTIAL_CONTENT" => StatusCode::PARTIAL_CONTENT,
-                "MULTI_STATUS" => StatusCode::MULTI_STATUS,
-                "ALREADY_REPORTED" => StatusCode::ALREADY_REPORTED,
-                "IM_USED" => StatusCode::IM_USED,
-                "MULTIPLE_CHOICES" => StatusCode::MULTIPLE_CHOICES,
-                "MOVED_PERMANENTLY" => StatusCode::MOVED_PERMANENTLY,
-                "FOUND" => StatusCode::FOUND,
-                "SEE_OTHER" => StatusCode::SEE_OTHER,
-                "NOT_MODIFIED" => StatusCode::NOT_MODIFIED,
-                "USE_PROXY" => StatusCode::USE_PROXY,
-                "TEMPORARY_REDIRECT" => StatusCode::TEMPORARY_REDIRECT,
-                "PERMANENT_REDIRECT" => StatusCode::PERMANENT_REDIRECT,
-                "BAD_REQUEST" => StatusCode::BAD_REQUEST,
-                "UNAUTHORIZED" => StatusCode::UNAUTHORIZED,
-                "PAYMENT_REQUIRED" => StatusCode::PAYMENT_REQUIRED,
-                "FORBIDDEN" => StatusCode::FORBIDDEN,
-                "NOT_FOUND" => StatusCode::NOT_FOUND,
-                "METHOD_NOT_ALLOWED" => StatusCode::METHOD_NOT_ALLOWED,
-                "NOT_ACCEPTABLE" => StatusCode::NOT_ACCEPTABLE,
-                "PROXY_AUTHENTICATION_REQUIRED" => StatusCode::PROXY_AUTHENTICATION_REQUIRED,
-                "REQUEST_TIMEOUT" => StatusCode::REQUEST_TIMEOUT,
-                "CONFLICT" => StatusCode::CONFLICT,
-                "GONE" => StatusCode::GONE,
-                "LENGTH_REQUIRED" => StatusCode::LENGTH_REQUIRED,
-                "PRECONDITION_FAILED" => StatusCode::PRECONDITION_FAILED,
-                "PAYLOAD_TOO_LARGE" => StatusCode::PAYLOAD_TOO_LARGE,
-                "URI_TOO_LONG" => StatusCode::URI_TOO_LONG,
-                "UNSUPPORTED_MEDIA_TYPE" => StatusCode::UNSUPPORTED_MEDIA_TYPE,
-                "RANGE_NOT_SATISFIABLE" => StatusCode::RANGE_NOT_SATISFIABLE,
-                "EXPECTATION_FAILED" => StatusCode::EXPECTATION_FAILED,
-                "IM_A_TEAPOT" => StatusCode::IM_A_TEAPOT,
-                "MISDIRECTED_REQUEST" => StatusCode::MISDIRECTED_REQUEST,
-                "UNPROCESSABLE_ENTITY" => StatusCode::UNPROCESSABLE_ENTITY,
-                "LOCKED" => StatusCode::LOCKED,
-                "FAILED_DEPENDENCY" => StatusCode::FAILED_DEPENDENCY,
-                "TOO_EARLY" => StatusCode::TOO_EARLY,
-                "UPGRADE_REQUIRED" => StatusCode::UPGRADE_REQUIRED,
-                "PRECONDITION_REQUIRED" => StatusCode::PRECONDITION_REQUIRED,
-                "TOO_MANY_REQUESTS" => StatusCode::TOO_MANY_REQUESTS,
-                "REQUEST_HEADER_FIELDS_TOO_LARGE" => StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE,
-                "UNAVAILABLE_FOR_LEGAL_REASONS" => StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS,
-                "INTERNAL_SERVER_ERROR" => StatusCode::INTERNAL_SERVER_ERROR,
-                "NOT_IMPLEMENTED" => StatusCode::NOT_IMPLEMENTED,
-                "BAD_GATEWAY" => StatusCode::BAD_GATEWAY,
-                "SERVICE_UNAVAILABLE" => StatusCode::SERVICE_UNAVAILABLE,
-                "GATEWAY_TIMEOUT" => StatusCode::GATEWAY_TIMEOUT,
-                "HTTP_VERSION_NOT_SUPPORTED" => StatusCode::HTTP_VERSION_NOT_SUPPORTED,
-                "VARIANT_ALSO_NEGOTIATES" => StatusCode::VARIANT_ALSO_NEGOTIATES,
-                "INSUFFICIENT_STORAGE" => StatusCode::INSUFFICIENT_STORAGE,
-                "LOOP_DETECTED" => StatusCode::LOOP_DETECTED,
-                "NOT_EXTENDED" => StatusCode::NOT_EXTENDED,
-                "NETWORK_AUTHENTICATION_REQUIRED" => StatusCode::NETWORK_AUTHENTICATION_REQUIRED,
-                _ => return Err(Error::new(ident.span(), "invalid HTTP status code")),
-            }),
-            RawStatusCode::Lit(lit) => StatusCode::from_u16(lit.base10_parse()?)
-                .map_err(|_| Error::new(lit.span(), "invalid HTTP status code")),
+            RawStatusCode::Ident(ident) => Ok(status_code_from_ident(ident)),
+            RawStatusCode::Lit(lit) => lit
+                .base10_parse()
+                .ok()
+                .and_then(|code| StatusCode::from_u16(code).ok())
+                .map(Some)
+                .ok_or_else(|| {
+                    Error::new(
+                        lit.span(),
+                        "invalid HTTP status code, expected a value from 100 to 999",
+                    )
+                }),
+            RawStatusCode::Expr(_) => Ok(None),
         }
     }
 }
 
+fn status_code_from_ident(ident: &Ident) -> Option<StatusCode> {
+    Some(match ident.to_string().as_str() {
+        "CONTINUE" => StatusCode::CONTINUE,
+        "SWITCHING_PROTOCOLS" => StatusCode::SWITCHING_PROTOCOLS,
+        "PROCESSING" => StatusCode::PROCESSING,
+        "EARLY_HINTS" => StatusCode::EARLY_HINTS,
+        "OK" => StatusCode::OK,
+        "CREATED" => StatusCode::CREATED,
+        "ACCEPTED" => StatusCode::ACCEPTED,
+        "NON_AUTHORITATIVE_INFORMATION" => StatusCode::NON_AUTHORITATIVE_INFORMATION,
+        "NO_CONTENT" => StatusCode::NO_CONTENT,
+        "RESET_CONTENT" => StatusCode::RESET_CONTENT,
+        "PARTIAL_CONTENT" => StatusCode::PARTIAL_CONTENT,
+        "MULTI_STATUS" => StatusCode::MULTI_STATUS,
+        "ALREADY_REPORTED" => StatusCode::ALREADY_REPORTED,
+        "IM_USED" => StatusCode::IM_USED,
+        "MULTIPLE_CHOICES" => StatusCode::MULTIPLE_CHOICES,
+        "MOVED_PERMANENTLY" => StatusCode::MOVED_PERMANENTLY,
+        "FOUND" => StatusCode::FOUND,
+        "SEE_OTHER" => StatusCode::SEE_OTHER,
+        "NOT_MODIFIED" => StatusCode::NOT_MODIFIED,
+        "USE_PROXY" => StatusCode::USE_PROXY,
+        "TEMPORARY_REDIRECT" => StatusCode::TEMPORARY_REDIRECT,
+        "PERMANENT_REDIRECT" => StatusCode::PERMANENT_REDIRECT,
+        "BAD_REQUEST" => StatusCode::BAD_REQUEST,
+        "UNAUTHORIZED" => StatusCode::UNAUTHORIZED,
+        "PAYMENT_REQUIRED" => StatusCode::PAYMENT_REQUIRED,
+        "FORBIDDEN" => StatusCode::FORBIDDEN,
+        "NOT_FOUND" => StatusCode::NOT_FOUND,
+        "METHOD_NOT_ALLOWED" => StatusCode::METHOD_NOT_ALLOWED,
+        "NOT_ACCEPTABLE" => StatusCode::NOT_ACCEPTABLE,
+        "PROXY_AUTHENTICATION_REQUIRED" => StatusCode::PROXY_AUTHENTICATION_REQUIRED,
+        "REQUEST_TIMEOUT" => StatusCode::REQUEST_TIMEOUT,
+        "CONFLICT" => StatusCode::CONFLICT,
+        "GONE" => StatusCode::GONE,
+        "LENGTH_REQUIRED" => StatusCode::LENGTH_REQUIRED,
+        "PRECONDITION_FAILED" => StatusCode::PRECONDITION_FAILED,
+        "PAYLOAD_TOO_LARGE" => StatusCode::PAYLOAD_TOO_LARGE,
+        "URI_TOO_LONG" => StatusCode::URI_TOO_LONG,
+        "UNSUPPORTED_MEDIA_TYPE" => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        "RANGE_NOT_SATISFIABLE" => StatusCode::RANGE_NOT_SATISFIABLE,
+        "EXPECTATION_FAILED" => StatusCode::EXPECTATION_FAILED,
+        "IM_A_TEAPOT" => StatusCode::IM_A_TEAPOT,
+        "MISDIRECTED_REQUEST" => StatusCode::MISDIRECTED_REQUEST,
+        "UNPROCESSABLE_ENTITY" => StatusCode::UNPROCESSABLE_ENTITY,
+        "LOCKED" => StatusCode::LOCKED,
+        "FAILED_DEPENDENCY" => StatusCode::FAILED_DEPENDENCY,
+        "TOO_EARLY" => StatusCode::TOO_EARLY,
+        "UPGRADE_REQUIRED" => StatusCode::UPGRADE_REQUIRED,
+        "PRECONDITION_REQUIRED" => StatusCode::PRECONDITION_REQUIRED,
+        "TOO_MANY_REQUESTS" => StatusCode::TOO_MANY_REQUESTS,
+        "REQUEST_HEADER_FIELDS_TOO_LARGE" => StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE,
+        "UNAVAILABLE_FOR_LEGAL_REASONS" => StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS,
+        "INTERNAL_SERVER_ERROR" => StatusCode::INTERNAL_SERVER_ERROR,
+        "NOT_IMPLEMENTED" => StatusCode::NOT_IMPLEMENTED,
+        "BAD_GATEWAY" => StatusCode::BAD_GATEWAY,
+        "SERVICE_UNAVAILABLE" => StatusCode::SERVICE_UNAVAILABLE,
+        "GATEWAY_TIMEOUT" => StatusCode::GATEWAY_TIMEOUT,
+        "HTTP_VERSION_NOT_SUPPORTED" => StatusCode::HTTP_VERSION_NOT_SUPPORTED,
+        "VARIANT_ALSO_NEGOTIATES" => StatusCode::VARIANT_ALSO_NEGOTIATES,
+        "INSUFFICIENT_STORAGE" => StatusCode::INSUFFICIENT_STORAGE,
+        "LOOP_DETECTED" => StatusCode::LOOP_DETECTED,
+        "NOT_EXTENDED" => StatusCode::NOT_EXTENDED,
+        "NETWORK_AUTHENTICATION_REQUIRED" => StatusCode::NETWORK_AUTHENTICATION_REQUIRED,
+        _ => return None,
+    })
+}
+
 impl Parse for RawStatusCode {
     fn parse(input: ParseStream) -> Result<Self> {
-        let lookahead = input.lookahead1();
-        if lookahead.peek(Ident) {
-            input.parse().map(RawStatusCode::Ident)
-        } else if lookahead.peek(LitInt) {
-            input.parse().map(RawStatusCode::Lit)
-        } else {
-            Err(lookahead.error())
-        }
+        Ok(match input.parse()? {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(lit), ..
+            }) => RawStatusCode::Lit(lit),
+            Expr::Path(expr) if expr.qself.is_none() && expr.path.get_ident().is_some() => {
+                let ident = expr.path.get_ident().expect("ident");
+
+                // Bare identifiers are status code constants, a typo should not turn them into runtime statuses.
+                if status_code_from_ident(ident).is_none() {
+                    return Err(Error::new(
+                        ident.span(),
+                        "invalid HTTP status code, use a path (e.g. `consts::CLIENT_CLOSED`), `self.` expression or block for a status determined at runtime",
+                    ));
+                }
+
+                RawStatusCode::Ident(ident.clone())
+            }
+            // Constants and expressions, e.g. `consts::CLIENT_CLOSED` or `self.status`.
+            expr @ (Expr::Path(_) | Expr::Block(_)) => RawStatusCode::Expr(Box::new(expr)),
+            expr if is_self_expr(&expr) => RawStatusCode::Expr(Box::new(expr)),
+            expr => {
+                return Err(Error::new_spanned(
+                    expr,
+                    "expected HTTP status code, path, `self.` expression or block",
+                ));
+            }
+        })
+    }
+}
+
+// Expressions on the error, e.g. `self.status` or `self.status()`.
+fn is_self_expr(expr: &Expr) -> bool {
+    match expr {
+        Expr::Field(expr) => is_self_expr(&expr.base),
+        Expr::MethodCall(expr) => is_self_expr(&expr.receiver),
+        Expr::Path(expr) => expr.qself.is_none() && expr.path.is_ident("self"),
+        _ => false,
     }
 }
@@ -0,0 +1,109 @@
+//! Implementation details of the derive macro.
+//!
+//! The [`InternalError`] traits select the most specific implementation by auto-ref, so the derive macro can
+//! convert a payload into an error without knowing whether it implements [`Error`].
+//! The [`ErrorMessage`] traits do the same for the [`Display`] message of an error.
+//! The [`ContextClone`] traits do the same for cloning the context fields of a masked transparent error.
+
+use std::{error::Error, fmt::Display};
+
+pub struct Internal<'a, T>(pub &'a T);
+
+// Manual implementations, as derives would require `T: Clone`. The fallback takes `self` by value through a reference.
+impl<T> Clone for Internal<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Internal<'_, T> {}
+
+pub trait InternalError<'a> {
+    fn internal_error(self) -> Option<&'a (dyn Error + 'static)>;
+}
+
+impl<'a, T: Error + 'static> InternalError<'a> for &&Internal<'a, T> {
+    fn internal_error(self) -> Option<&'a (dyn Error + 'static)> {
+        Some(self.0)
+    }
+}
+
+pub trait InternalErrorRef<'a> {
+    fn internal_error(self) -> Option<&'a (dyn Error + 'static)>;
+}
+
+impl<'a, T: AsRef<dyn Error + Send + Sync + 'static>> InternalErrorRef<'a> for &Internal<'a, T> {
+    fn internal_error(self) -> Option<&'a (dyn Error + 'static)> {
+        Some(self.0.as_ref())
+    }
+}
+
+pub trait InternalErrorNone<'a> {
+    fn internal_error(self) -> Option<&'a (dyn Error + 'static)>;
+}
+
+impl<'a, T> InternalErrorNone<'a> for Internal<'a, T> {
+    fn internal_error(self) -> Option<&'a (dyn Error + 'static)> {
+        None
+    }
+}
+
+pub struct Message<'a, T: ?Sized>(pub &'a T);
+
+impl<T: ?Sized> Clone for Message<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for Message<'_, T> {}
+
+pub trait ErrorMessage {
+    fn error_message(self) -> Option<String>;
+}
+
+impl<T: Display + ?Sized> ErrorMessage for &Message<'_, T> {
+    fn error_message(self) -> Option<String> {
+        Some(self.0.to_string())
+    }
+}
+
+pub trait ErrorMessageNone {
+    fn error_message(self) -> Option<String>;
+}
+
+impl<T: ?Sized> ErrorMessageNone for Message<'_, T> {
+    fn error_message(self) -> Option<String> {
+        None
+    }
+}
+
+pub struct Context<'a, T>(pub &'a T);
+
+impl<T> Clone for Context<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Context<'_, T> {}
+
+pub trait ContextClone<T> {
+    fn context_clone(self) -> Option<T>;
+}
+
+impl<T: Clone> ContextClone<T> for &Context<'_, T> {
+    fn context_clone(self) -> Option<T> {
+        Some(self.0.clone())
+    }
+}
+
+pub trait ContextCloneNone<T> {
+    fn context_clone(self) -> Option<T>;
+}
+
+impl<T> ContextCloneNone<T> for Context<'_, T> {
+    fn context_clone(self) -> Option<T> {
+        None
+    }
+}
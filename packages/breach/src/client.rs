@@ -0,0 +1,260 @@
+//! Client utilities.
+
+// The retry delay requires a Tokio timer, which is not available on `wasm32`.
+#[cfg(all(feature = "reqwest-middleware", not(target_arch = "wasm32")))]
+mod retry;
+
+use std::{
+    error::Error,
+    fmt::{self, Debug, Display},
+    sync::Arc,
+};
+
+use bytes::Bytes;
+use http::{Response, StatusCode};
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, DeserializeOwned, IgnoredAny},
+};
+use serde_json::Value;
+
+use crate::{HttpError, is_retryable_status};
+
+#[cfg(all(feature = "reqwest-middleware", not(target_arch = "wasm32")))]
+pub use retry::*;
+pub use serde;
+
+/// HTTP error with a client-side mirror type.
+///
+/// Derived with `#[http(mirror)]`. For enums, the mirror type is an enum named after the error with a `Response`
+/// suffix, in which skipped payloads become unit variants and delegating variants contain the mirror of the nested
+/// error. For structs, the mirror type is the struct itself, which should implement [`Deserialize`].
+///
+/// Nested delegating variants are not flattened into the outer mirror, as the derive macro can't see the variants of
+/// the nested error. The mirror is nested the same way as the error, e.g.
+/// `UpdateUserErrorResponse::GetUserById(GetUserByIdErrorResponse::NotFound(..))`, while the body on the wire is
+/// flat, as with the error itself.
+pub trait HttpErrorMirror {
+    /// Client-side mirror type.
+    type Mirror: HttpError + DeserializeOwned;
+}
+
+impl<T: HttpErrorMirror + ?Sized> HttpErrorMirror for &T {
+    type Mirror = T::Mirror;
+}
+
+impl<T: HttpErrorMirror> HttpErrorMirror for Box<T> {
+    type Mirror = T::Mirror;
+}
+
+impl<T: HttpErrorMirror> HttpErrorMirror for Arc<T> {
+    type Mirror = T::Mirror;
+}
+
+/// Error of a decoded response.
+#[derive(Debug)]
+pub enum ClientError<E> {
+    /// Documented HTTP error.
+    Http(E),
+    /// Undocumented response.
+    Unknown {
+        /// HTTP status code.
+        status: StatusCode,
+        /// Response body.
+        body: Bytes,
+    },
+    /// Successful response with a body which could not be deserialized.
+    Deserialize(serde_json::Error),
+    /// Request error.
+    #[cfg(feature = "reqwest")]
+    Reqwest(reqwest::Error),
+}
+
+impl<E> ClientError<E> {
+    /// HTTP status code, if a response was received.
+    pub fn status(&self) -> Option<StatusCode>
+    where
+        E: HttpError,
+    {
+        match self {
+            ClientError::Http(error) => Some(error.status()),
+            ClientError::Unknown { status, .. } => Some(*status),
+            ClientError::Deserialize(_) => None,
+            #[cfg(feature = "reqwest")]
+            ClientError::Reqwest(error) => error.status(),
+        }
+    }
+
+    /// Whether the request may succeed when retried.
+    ///
+    /// Documented HTTP errors are retryable according to [`HttpError::is_retryable`], undocumented responses
+    /// according to [`is_retryable_status`]. Request errors are retryable if they are timeouts or connection errors.
+    pub fn is_retryable(&self) -> bool
+    where
+        E: HttpError,
+    {
+        match self {
+            ClientError::Http(error) => error.is_retryable(),
+            ClientError::Unknown { status, .. } => is_retryable_status(*status),
+            ClientError::Deserialize(_) => false,
+            #[cfg(feature = "reqwest")]
+            ClientError::Reqwest(error) => is_retryable_reqwest(error),
+        }
+    }
+}
+
+impl<E: HttpError> Display for ClientError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Http(error) => match error.code() {
+                Some(code) => write!(f, "HTTP error {} ({code})", error.status()),
+                None => write!(f, "HTTP error {}", error.status()),
+            },
+            ClientError::Unknown { status, .. } => write!(f, "unknown HTTP error {status}"),
+            ClientError::Deserialize(error) => write!(f, "invalid response body: {error}"),
+            #[cfg(feature = "reqwest")]
+            ClientError::Reqwest(error) => write!(f, "request failed: {error}"),
+        }
+    }
+}
+
+impl<E: HttpError + Debug> Error for ClientError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ClientError::Http(_) | ClientError::Unknown { .. } => None,
+            ClientError::Deserialize(error) => Some(error),
+            #[cfg(feature = "reqwest")]
+            ClientError::Reqwest(error) => Some(error),
+        }
+    }
+}
+
+/// Placeholder for error data which is not exposed to clients.
+///
+/// Use it in place of payloads skipped with `#[serde(skip)]` on the server, e.g. `Internal(Opaque)`.
+/// It deserializes from any value, so these variants decode instead of failing.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Opaque;
+
+impl Display for Opaque {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "opaque error")
+    }
+}
+
+impl Error for Opaque {}
+
+impl Serialize for Opaque {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_unit()
+    }
+}
+
+impl<'de> Deserialize<'de> for Opaque {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        IgnoredAny::deserialize(deserializer).map(|_| Opaque)
+    }
+}
+
+/// Decode a response into its successful body or a documented HTTP error.
+///
+/// Error bodies are decoded into `E` using its discriminator tag. If this fails, or the status of the decoded
+/// error does not match the response status, the response is returned as [`ClientError::Unknown`].
+pub fn decode<T, E>(response: Response<Bytes>) -> Result<T, ClientError<E>>
+where
+    T: DeserializeOwned,
+    E: HttpError + DeserializeOwned,
+{
+    let (parts, body) = response.into_parts();
+
+    decode_body(parts.status, body)
+}
+
+/// Decode a [`reqwest::Response`] into its successful body or a documented HTTP error.
+///
+/// See [`decode`].
+#[cfg(feature = "reqwest")]
+pub async fn decode_reqwest<T, E>(response: reqwest::Response) -> Result<T, ClientError<E>>
+where
+    T: DeserializeOwned,
+    E: HttpError + DeserializeOwned,
+{
+    let status = response.status();
+    let body = response.bytes().await.map_err(ClientError::Reqwest)?;
+
+    decode_body(status, body)
+}
+
+#[cfg(feature = "reqwest")]
+fn is_retryable_reqwest(error: &reqwest::Error) -> bool {
+    #[cfg(not(target_arch = "wasm32"))]
+    if error.is_connect() {
+        return true;
+    }
+
+    error.is_timeout()
+}
+
+/// Decode a response body into its successful value or a documented HTTP error.
+///
+/// See [`decode`].
+pub fn decode_body<T, E>(status: StatusCode, body: Bytes) -> Result<T, ClientError<E>>
+where
+    T: DeserializeOwned,
+    E: HttpError + DeserializeOwned,
+{
+    if status.is_success() {
+        if body.is_empty() {
+            T::deserialize(Value::Null)
+        } else {
+            serde_json::from_slice(&body)
+        }
+        .map_err(ClientError::Deserialize)
+    } else {
+        Err(decode_error(status, body))
+    }
+}
+
+/// Decode an error response body into a documented HTTP error.
+///
+/// See [`decode`].
+pub fn decode_error<E>(status: StatusCode, body: Bytes) -> ClientError<E>
+where
+    E: HttpError + DeserializeOwned,
+{
+    // Nested error enums repeat the discriminator tag, deserializing into a value first keeps the innermost one.
+    match serde_json::from_slice::<Value>(&body).and_then(E::deserialize) {
+        Ok(error) if error.status() == status => ClientError::Http(error),
+        _ => ClientError::Unknown { status, body },
+    }
+}
+
+#[doc(hidden)]
+pub type MirrorCandidate<M> = fn(&Value) -> Option<M>;
+
+#[doc(hidden)]
+pub fn deserialize_mirror<'de, D, M>(
+    deserializer: D,
+    tag: &str,
+    variants: &[MirrorCandidate<M>],
+    fallbacks: &[MirrorCandidate<M>],
+) -> Result<M, D::Error>
+where
+    D: Deserializer<'de>,
+    M: HttpError,
+{
+    let value = Value::deserialize(deserializer)?;
+    let code = value
+        .get(tag)
+        .and_then(Value::as_str)
+        .ok_or_else(|| de::Error::custom(format_args!("missing field `{tag}`")))?;
+
+    // The tag of nested errors takes precedence, so the variant is the one whose error code matches the tag.
+    // Payloads with their own tag can't be matched on error code, these are attempted last.
+    variants
+        .iter()
+        .filter_map(|variant| variant(&value))
+        .find(|error| error.code() == Some(code))
+        .or_else(|| fallbacks.iter().find_map(|variant| variant(&value)))
+        .ok_or_else(|| de::Error::custom(format_args!("unknown error code `{code}`")))
+}
@@ -0,0 +1,190 @@
+use std::{
+    fmt::{self, Debug},
+    marker::PhantomData,
+    time::{Duration, SystemTime},
+};
+
+use bytes::Bytes;
+use http::{Extensions, HeaderMap, StatusCode, header::RETRY_AFTER};
+use reqwest::{Request, Response, ResponseBuilderExt};
+use reqwest_middleware::{Middleware, Next};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    HttpError,
+    client::{ClientError, decode_error, is_retryable_reqwest},
+};
+
+/// [`reqwest_middleware`] middleware retrying requests which failed with a retryable error.
+///
+/// Error responses are decoded into `E`, usually the mirror type of the server's error (see
+/// [`HttpErrorMirror`](crate::client::HttpErrorMirror)), and retried if [`ClientError::is_retryable`].
+/// The delay before a retry is the [`HttpError::retry_after`] of the error, the `Retry-After` header of the response
+/// (in seconds or as HTTP date) or an exponential backoff, in that order.
+///
+/// Requests with a body which can't be cloned (e.g. a stream) are not retried. The middleware waits with a Tokio
+/// timer, so it is not available on `wasm32`.
+pub struct RetryMiddleware<E> {
+    max_retries: u32,
+    backoff: Duration,
+    max_backoff: Duration,
+    error: PhantomData<fn() -> E>,
+}
+
+impl<E> RetryMiddleware<E> {
+    /// Create the middleware with the default of 3 retries and a backoff from 100 milliseconds up to 10 seconds.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of retries.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the backoff before the first retry, it doubles for each subsequent retry.
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Set the maximum backoff.
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    fn backoff_for(&self, attempt: u32) -> Duration {
+        self.backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff)
+    }
+
+    fn delay(
+        &self,
+        attempt: u32,
+        status: StatusCode,
+        headers: &HeaderMap,
+        body: Bytes,
+    ) -> Option<Duration>
+    where
+        E: HttpError + DeserializeOwned,
+    {
+        let error = decode_error::<E>(status, body);
+        if !error.is_retryable() {
+            return None;
+        }
+
+        let retry_after = match &error {
+            ClientError::Http(error) => error.retry_after(),
+            _ => None,
+        };
+
+        Some(
+            retry_after
+                .or_else(|| retry_after_header(headers))
+                .unwrap_or_else(|| self.backoff_for(attempt)),
+        )
+    }
+}
+
+impl<E> Debug for RetryMiddleware<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryMiddleware")
+            .field("max_retries", &self.max_retries)
+            .field("backoff", &self.backoff)
+            .field("max_backoff", &self.max_backoff)
+            .finish()
+    }
+}
+
+impl<E> Default for RetryMiddleware<E> {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            error: PhantomData,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<E> Middleware for RetryMiddleware<E>
+where
+    E: HttpError + DeserializeOwned + 'static,
+{
+    async fn handle(
+        &self,
+        mut request: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let mut attempt = 0;
+
+        loop {
+            let retry = if attempt < self.max_retries {
+                request.try_clone()
+            } else {
+                None
+            };
+
+            let result = next.clone().run(request, extensions).await;
+
+            let Some(retry) = retry else {
+                return result;
+            };
+
+            let delay = match result {
+                Ok(response) if !response.status().is_success() => {
+                    let status = response.status();
+                    let version = response.version();
+                    let url = response.url().clone();
+                    let headers = response.headers().clone();
+                    let body = response.bytes().await?;
+
+                    match self.delay(attempt, status, &headers, body.clone()) {
+                        Some(delay) => delay,
+                        None => {
+                            // The body has been consumed to decode the error, so the response is rebuilt.
+                            let mut builder = http::Response::builder()
+                                .status(status)
+                                .version(version)
+                                .url(url);
+                            if let Some(builder_headers) = builder.headers_mut() {
+                                *builder_headers = headers;
+                            }
+
+                            return Ok(Response::from(
+                                builder.body(body).expect("response should be valid"),
+                            ));
+                        }
+                    }
+                }
+                Err(reqwest_middleware::Error::Reqwest(error)) if is_retryable_reqwest(&error) => {
+                    self.backoff_for(attempt)
+                }
+                result => return result,
+            };
+
+            tokio::time::sleep(delay).await;
+
+            request = retry;
+            attempt += 1;
+        }
+    }
+}
+
+// `Retry-After` is either delay seconds or an HTTP date (RFC 9110, section 10.2.3).
+fn retry_after_header(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    match value.parse() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => httpdate::parse_http_date(value).ok().map(|date| {
+            date.duration_since(SystemTime::now())
+                .unwrap_or(Duration::ZERO)
+        }),
+    }
+}
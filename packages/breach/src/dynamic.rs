@@ -0,0 +1,155 @@
+use std::{fmt, ops::Deref};
+
+use http::{HeaderValue, Response, StatusCode, header};
+use serde::{Serialize, Serializer};
+
+use crate::{Exposed, HttpError};
+
+/// Object-safe HTTP error with erased serialization, e.g. to collect errors of different types as
+/// `Box<dyn DynHttpError>`.
+///
+/// Implemented for all HTTP errors which implement [`Serialize`].
+pub trait DynHttpError: HttpError + erased_serde::Serialize + Send + Sync {
+    /// [`HttpError::masked`] of the HTTP error, boxed.
+    fn masked_dyn(&self) -> Option<BoxHttpError>;
+}
+
+impl<E: HttpError + Serialize + Send + Sync + 'static> DynHttpError for E {
+    fn masked_dyn(&self) -> Option<BoxHttpError> {
+        self.masked().map(BoxHttpError::new)
+    }
+}
+
+erased_serde::serialize_trait_object!(DynHttpError);
+
+/// Boxed HTTP error of any type, converted from any HTTP error which implements [`Serialize`].
+///
+/// The HTTP error is serialized according to its [`Exposure`](crate::Exposure), with `code` as tag.
+pub struct BoxHttpError {
+    error: Box<dyn DynHttpError>,
+    tag: Option<&'static str>,
+    #[cfg(feature = "request-id")]
+    request_id: bool,
+}
+
+impl BoxHttpError {
+    /// Box an HTTP error.
+    pub fn new<E: HttpError + Serialize + Send + Sync + 'static>(error: E) -> Self {
+        Self {
+            error: Box::new(error),
+            tag: Some("code"),
+            #[cfg(feature = "request-id")]
+            request_id: false,
+        }
+    }
+
+    /// Set the tag of the error code, serialized instead of internal payloads in production.
+    pub fn with_tag(mut self, tag: Option<&'static str>) -> Self {
+        self.tag = tag;
+        self
+    }
+
+    /// Set whether the response body contains the request ID of the current request, as with `#[http(request_id)]`.
+    #[cfg(feature = "request-id")]
+    pub fn with_request_id(mut self, request_id: bool) -> Self {
+        self.request_id = request_id;
+        self
+    }
+
+    /// Boxed HTTP error.
+    pub fn into_inner(self) -> Box<dyn DynHttpError> {
+        self.error
+    }
+}
+
+impl<E: HttpError + Serialize + Send + Sync + 'static> From<E> for BoxHttpError {
+    fn from(value: E) -> Self {
+        Self::new(value)
+    }
+}
+
+impl Deref for BoxHttpError {
+    type Target = dyn DynHttpError;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.error
+    }
+}
+
+impl AsRef<dyn DynHttpError> for BoxHttpError {
+    fn as_ref(&self) -> &(dyn DynHttpError + 'static) {
+        &*self.error
+    }
+}
+
+impl fmt::Debug for BoxHttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoxHttpError")
+            .field("status", &self.error.status())
+            .field("path", &self.error.path())
+            .finish()
+    }
+}
+
+/// Serialized according to the [`Exposure`](crate::Exposure) of the HTTP error, e.g. when nested in another body.
+impl Serialize for BoxHttpError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Exposed::new(&*self.error, self.tag).serialize(serializer)
+    }
+}
+
+/// HTTP response with a JSON body.
+///
+/// Calls [`notify`](crate::notify) with the HTTP error and responds with its [`HttpError::masked`] error.
+impl From<BoxHttpError> for Response<Vec<u8>> {
+    fn from(value: BoxHttpError) -> Self {
+        crate::notify(&*value.error);
+
+        let masked = value.error.masked_dyn();
+        let error = masked
+            .as_ref()
+            .map_or(&*value.error, |masked| &*masked.error);
+
+        let exposed = Exposed::new(error, value.tag);
+        #[cfg(feature = "request-id")]
+        let body = if value.request_id {
+            serde_json::to_vec(&crate::request_id::WithRequestId::new(&exposed))
+        } else {
+            serde_json::to_vec(&exposed)
+        };
+        #[cfg(not(feature = "request-id"))]
+        let body = serde_json::to_vec(&exposed);
+
+        let (status, body) = match body {
+            Ok(body) => (error.status(), body),
+            Err(err) => {
+                crate::notify(&crate::notify::EncodeError(err.into()));
+
+                (StatusCode::INTERNAL_SERVER_ERROR, b"{}".to_vec())
+            }
+        };
+
+        let mut response = Response::new(body);
+        *response.status_mut() = status;
+        response.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+
+        if let Some(retry_after) = error.retry_after() {
+            response.headers_mut().insert(
+                header::RETRY_AFTER,
+                HeaderValue::from(retry_after.as_secs()),
+            );
+        }
+
+        response
+    }
+}
+
+#[cfg(feature = "axum")]
+impl axum_core::response::IntoResponse for BoxHttpError {
+    fn into_response(self) -> axum_core::response::Response {
+        Response::<Vec<u8>>::from(self).map(axum_core::body::Body::from)
+    }
+}
@@ -1,10 +1,188 @@
+use std::{error::Error, sync::Arc, time::Duration};
+
 use http::StatusCode;
 
+use crate::{Exposure, Level};
+
 /// HTTP error.
 pub trait HttpError {
     /// HTTP status code.
     fn status(&self) -> StatusCode;
 
+    /// Error code, e.g. the `code` tag of a serialized error enum.
+    fn code(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Reason phrase of the HTTP status code, e.g. set with `#[http(reason = ..)]`.
+    ///
+    /// Defaults to the canonical reason of the HTTP status code.
+    fn reason(&self) -> Option<&'static str> {
+        self.status().canonical_reason()
+    }
+
+    /// Whether the request may succeed when retried.
+    ///
+    /// Defaults to [`is_retryable_status`] of the HTTP status code.
+    fn is_retryable(&self) -> bool {
+        is_retryable_status(self.status())
+    }
+
+    /// Delay before the request should be retried.
+    fn retry_after(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Severity of the HTTP error.
+    ///
+    /// Defaults to [`Level::from_status`] of the HTTP status code.
+    fn level(&self) -> Level {
+        Level::from_status(self.status())
+    }
+
+    /// Key of the localized message of the HTTP error, e.g. a Fluent message ID.
+    ///
+    /// Defaults to the error code.
+    fn message_key(&self) -> Option<&'static str> {
+        self.code()
+    }
+
+    /// Message keys of all variants of the HTTP error, e.g. to check that localization catalogs are complete.
+    fn message_keys() -> Vec<&'static str>
+    where
+        Self: Sized,
+    {
+        Vec::new()
+    }
+
+    /// Exposure of the payload of the HTTP error to clients.
+    fn exposure(&self) -> Exposure {
+        Exposure::Public
+    }
+
+    /// HTTP error sent to clients instead of this error, e.g. a `NotFound` error instead of a `Forbidden` error to
+    /// hide the existence of a resource.
+    ///
+    /// Hooks and integrations still receive the original error.
+    fn masked(&self) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
+    /// Internal error which is not exposed to clients, e.g. a skipped `anyhow::Error` payload.
+    fn internal(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+
+    /// Whether the HTTP error should be reported to an error tracker, e.g. Sentry.
+    ///
+    /// Defaults to `true` for server errors.
+    fn is_reported(&self) -> bool {
+        self.status().is_server_error()
+    }
+
+    /// Types and variants of the HTTP error, e.g. `["GetUserByIdError", "NotFound", "NotFoundError"]`.
+    fn path(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
     /// Hook called when the HTTP error is used as response.
     fn hook(&self);
 }
+
+macro_rules! delegate_http_error {
+    () => {
+        fn status(&self) -> StatusCode {
+            (**self).status()
+        }
+
+        fn code(&self) -> Option<&'static str> {
+            (**self).code()
+        }
+
+        fn reason(&self) -> Option<&'static str> {
+            (**self).reason()
+        }
+
+        fn is_retryable(&self) -> bool {
+            (**self).is_retryable()
+        }
+
+        fn retry_after(&self) -> Option<Duration> {
+            (**self).retry_after()
+        }
+
+        fn level(&self) -> Level {
+            (**self).level()
+        }
+
+        fn message_key(&self) -> Option<&'static str> {
+            (**self).message_key()
+        }
+
+        fn exposure(&self) -> Exposure {
+            (**self).exposure()
+        }
+
+        fn internal(&self) -> Option<&(dyn Error + 'static)> {
+            (**self).internal()
+        }
+
+        fn is_reported(&self) -> bool {
+            (**self).is_reported()
+        }
+
+        fn path(&self) -> Vec<&'static str> {
+            (**self).path()
+        }
+
+        fn hook(&self) {
+            (**self).hook()
+        }
+    };
+}
+
+impl<T: HttpError + ?Sized> HttpError for &T {
+    delegate_http_error!();
+}
+
+impl<T: HttpError> HttpError for Box<T> {
+    delegate_http_error!();
+
+    fn masked(&self) -> Option<Self> {
+        (**self).masked().map(Box::new)
+    }
+
+    fn message_keys() -> Vec<&'static str> {
+        T::message_keys()
+    }
+}
+
+impl<T: HttpError> HttpError for Arc<T> {
+    delegate_http_error!();
+
+    fn masked(&self) -> Option<Self> {
+        (**self).masked().map(Arc::new)
+    }
+
+    fn message_keys() -> Vec<&'static str> {
+        T::message_keys()
+    }
+}
+
+/// Whether a request which failed with the status code may succeed when retried.
+///
+/// These are `408 Request Timeout`, `429 Too Many Requests`, `502 Bad Gateway`, `503 Service Unavailable`
+/// and `504 Gateway Timeout`.
+pub fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::REQUEST_TIMEOUT
+            | StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
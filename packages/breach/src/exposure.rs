@@ -0,0 +1,131 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "expose")]
+use std::{collections::BTreeMap, fmt::Display};
+
+#[cfg(feature = "expose")]
+use serde::{Serialize, Serializer, ser::SerializeMap};
+
+#[cfg(feature = "expose")]
+use crate::HttpError;
+
+static DEVELOPMENT: AtomicBool = AtomicBool::new(false);
+
+/// Exposure of the details of an HTTP error to clients.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Exposure {
+    /// The payload is part of the response body.
+    #[default]
+    Public,
+    /// The payload is only part of the response body in [`Environment::Development`].
+    Internal,
+}
+
+/// Environment determining which details of HTTP errors are exposed to clients.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Environment {
+    /// Only public payloads are exposed.
+    #[default]
+    Production,
+    /// All payloads are exposed, including the message and sources of internal errors.
+    Development,
+}
+
+/// Current environment, defaults to [`Environment::Production`].
+pub fn environment() -> Environment {
+    if DEVELOPMENT.load(Ordering::Relaxed) {
+        Environment::Development
+    } else {
+        Environment::Production
+    }
+}
+
+/// Set the current environment.
+pub fn set_environment(environment: Environment) {
+    DEVELOPMENT.store(environment == Environment::Development, Ordering::Relaxed);
+}
+
+/// HTTP error serialized according to its [`Exposure`] and the current [`Environment`].
+///
+/// In production, errors with [`Exposure::Internal`] only serialize their tag (e.g. `{"code":"internal"}`).
+/// In development, errors serialize completely, with `detail` and `sources` members containing the message and
+/// sources of the [`HttpError::internal`] error.
+#[cfg(feature = "expose")]
+pub struct Exposed<'a, E: ?Sized> {
+    error: &'a E,
+    tag: Option<&'static str>,
+}
+
+#[cfg(feature = "expose")]
+impl<'a, E: ?Sized> Exposed<'a, E> {
+    /// Wrap an HTTP error, serialized with the error code as `tag` in production.
+    pub fn new(error: &'a E, tag: Option<&'static str>) -> Self {
+        Self { error, tag }
+    }
+}
+
+#[cfg(feature = "expose")]
+#[derive(Serialize)]
+struct Development<'a, E: ?Sized> {
+    #[serde(flatten)]
+    error: &'a E,
+    detail: String,
+    sources: Vec<String>,
+}
+
+#[cfg(feature = "expose")]
+impl<E: HttpError + Serialize + ?Sized> Serialize for Exposed<'_, E> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match environment() {
+            Environment::Production => match self.error.exposure() {
+                Exposure::Public => self.error.serialize(serializer),
+                Exposure::Internal => {
+                    let mut map = serializer.serialize_map(None)?;
+                    if let (Some(tag), Some(code)) = (self.tag, self.error.code()) {
+                        map.serialize_entry(tag, code)?;
+                    }
+                    map.end()
+                }
+            },
+            Environment::Development => match self.error.internal() {
+                Some(internal) => Development {
+                    error: self.error,
+                    detail: internal.to_string(),
+                    sources: std::iter::successors(internal.source(), |&error| error.source())
+                        .map(ToString::to_string)
+                        .collect(),
+                }
+                .serialize(serializer),
+                None => self.error.serialize(serializer),
+            },
+        }
+    }
+}
+
+/// Serialized HTTP error with its message (e.g. `message` or `detail`), which is omitted for errors with
+/// [`Exposure::Internal`] in production.
+#[cfg(feature = "expose")]
+#[derive(Serialize)]
+pub struct WithMessage<'a, B: ?Sized> {
+    #[serde(flatten)]
+    body: &'a B,
+    #[serde(flatten)]
+    message: Option<BTreeMap<&'static str, String>>,
+}
+
+#[cfg(feature = "expose")]
+impl<'a, B: ?Sized> WithMessage<'a, B> {
+    /// Wrap a serialized HTTP error (e.g. the error itself or [`Exposed`]) with the message of the error as `field`.
+    pub fn new<E: HttpError + Display + ?Sized>(
+        body: &'a B,
+        error: &E,
+        field: &'static str,
+    ) -> Self {
+        let exposed =
+            environment() == Environment::Development || error.exposure() == Exposure::Public;
+
+        Self {
+            body,
+            message: exposed.then(|| BTreeMap::from([(field, error.to_string())])),
+        }
+    }
+}
@@ -0,0 +1,323 @@
+//! Fluent localization utilities.
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use fluent_bundle::{
+    FluentArgs, FluentError, FluentResource, FluentValue, concurrent::FluentBundle,
+};
+use fluent_langneg::{NegotiationStrategy, accepted_languages, negotiate_languages};
+use http::{Request, header::ACCEPT_LANGUAGE};
+use serde::Serialize;
+use serde_json::Value;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{Environment, Exposure, HttpError, environment};
+
+pub use fluent_bundle;
+pub use unic_langid::{self, LanguageIdentifier};
+
+tokio::task_local! {
+    static LOCALES: (Arc<Localizer>, Vec<LanguageIdentifier>);
+}
+
+/// Error of a Fluent resource added to a [`Localizer`].
+#[derive(Debug)]
+pub enum LocalizeError {
+    /// The resource has syntax errors.
+    Parse(Vec<FluentError>),
+    /// The resource defines messages which are already defined.
+    Bundle(Vec<FluentError>),
+}
+
+impl Display for LocalizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LocalizeError::Parse(errors) => {
+                write!(f, "invalid Fluent resource ({} errors)", errors.len())
+            }
+            LocalizeError::Bundle(errors) => {
+                write!(f, "conflicting Fluent messages ({} errors)", errors.len())
+            }
+        }
+    }
+}
+
+impl Error for LocalizeError {}
+
+/// Localized message of an HTTP error.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Localized {
+    /// Title, the `.title` attribute of the Fluent message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Message, the value of the Fluent message.
+    pub message: String,
+}
+
+/// Catalogs of localized messages of HTTP errors, with a Fluent message for each [`HttpError::message_key`].
+///
+/// Public fields of the serialized error (e.g. `id`) are available as arguments (e.g. `{ $id }`).
+pub struct Localizer {
+    default: LanguageIdentifier,
+    locales: Vec<LanguageIdentifier>,
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+impl Localizer {
+    /// Create a localizer, falling back to the `default` locale.
+    pub fn new(default: LanguageIdentifier) -> Self {
+        Self {
+            default,
+            locales: Vec::new(),
+            bundles: Vec::new(),
+        }
+    }
+
+    /// Add a Fluent resource (e.g. the contents of an `.ftl` file) for a locale.
+    pub fn add_resource(
+        &mut self,
+        locale: LanguageIdentifier,
+        source: impl Into<String>,
+    ) -> Result<(), LocalizeError> {
+        let resource = FluentResource::try_new(source.into()).map_err(|(_, errors)| {
+            LocalizeError::Parse(errors.into_iter().map(FluentError::from).collect())
+        })?;
+
+        let index = match self.locales.iter().position(|existing| *existing == locale) {
+            Some(index) => index,
+            None => {
+                let mut bundle = FluentBundle::new_concurrent(vec![locale.clone()]);
+                // Unicode isolation marks are noise in JSON bodies.
+                bundle.set_use_isolating(false);
+
+                self.locales.push(locale);
+                self.bundles.push(bundle);
+                self.bundles.len() - 1
+            }
+        };
+
+        self.bundles[index]
+            .add_resource(resource)
+            .map_err(LocalizeError::Bundle)
+    }
+
+    /// Add a Fluent resource for a locale.
+    pub fn with_resource(
+        mut self,
+        locale: LanguageIdentifier,
+        source: impl Into<String>,
+    ) -> Result<Self, LocalizeError> {
+        self.add_resource(locale, source)?;
+
+        Ok(self)
+    }
+
+    /// Locales with Fluent resources.
+    pub fn locales(&self) -> &[LanguageIdentifier] {
+        &self.locales
+    }
+
+    /// Locales matching an `Accept-Language` header, by preference and ending with the default locale.
+    pub fn negotiate(&self, accept_language: Option<&str>) -> Vec<LanguageIdentifier> {
+        let requested = accept_language
+            .map(accepted_languages::parse)
+            .unwrap_or_default();
+
+        negotiate_languages(
+            &requested,
+            &self.locales,
+            Some(&self.default),
+            NegotiationStrategy::Filtering,
+        )
+        .into_iter()
+        .cloned()
+        .collect()
+    }
+
+    /// Localize an HTTP error in the first of the locales which has its message.
+    pub fn localize<E: HttpError + Serialize + ?Sized>(
+        &self,
+        error: &E,
+        locales: &[LanguageIdentifier],
+    ) -> Option<Localized> {
+        let key = error.message_key()?;
+
+        // Internal payloads are only exposed in development.
+        let args =
+            if environment() == Environment::Development || error.exposure() == Exposure::Public {
+                args(error)
+            } else {
+                None
+            };
+
+        locales
+            .iter()
+            .filter_map(|locale| self.bundle(locale))
+            .find_map(|bundle| {
+                let message = bundle.get_message(key)?;
+                let mut errors = Vec::new();
+
+                let title = message.get_attribute("title").map(|title| {
+                    bundle
+                        .format_pattern(title.value(), args.as_ref(), &mut errors)
+                        .into_owned()
+                });
+                let message = bundle
+                    .format_pattern(message.value()?, args.as_ref(), &mut errors)
+                    .into_owned();
+
+                Some(Localized { title, message })
+            })
+    }
+
+    /// Message keys of an HTTP error type without a message, for each locale.
+    ///
+    /// Use it in tests to keep the catalogs complete.
+    pub fn missing_keys<E: HttpError>(&self) -> Vec<(&LanguageIdentifier, &'static str)> {
+        let keys = E::message_keys();
+
+        self.locales
+            .iter()
+            .zip(&self.bundles)
+            .flat_map(|(locale, bundle)| {
+                keys.iter()
+                    .filter(|key| !bundle.has_message(key))
+                    .map(move |key| (locale, *key))
+            })
+            .collect()
+    }
+
+    fn bundle(&self, locale: &LanguageIdentifier) -> Option<&FluentBundle<FluentResource>> {
+        self.locales
+            .iter()
+            .position(|existing| existing == locale)
+            .map(|index| &self.bundles[index])
+    }
+}
+
+fn args<E: Serialize + ?Sized>(error: &E) -> Option<FluentArgs<'static>> {
+    let Ok(Value::Object(object)) = serde_json::to_value(error) else {
+        return None;
+    };
+
+    let mut args = FluentArgs::new();
+    for (name, value) in object {
+        match value {
+            Value::String(value) => args.set(name, value),
+            Value::Number(value) => {
+                if let Some(value) = value.as_f64() {
+                    args.set(name, value);
+                }
+            }
+            Value::Bool(value) => args.set(name, FluentValue::from(value.to_string())),
+            _ => {}
+        }
+    }
+
+    Some(args)
+}
+
+/// Localized message of an HTTP error for the current request, within a [`LocalizeLayer`].
+pub fn localize<E: HttpError + Serialize + ?Sized>(error: &E) -> Option<Localized> {
+    LOCALES
+        .try_with(|(localizer, locales)| localizer.localize(error, locales))
+        .ok()
+        .flatten()
+}
+
+/// Serialized HTTP error with the `title` and `message` members of its localized message.
+///
+/// Derived with `#[http(localize)]`.
+#[derive(Serialize)]
+pub struct WithLocalized<'a, B: ?Sized> {
+    #[serde(flatten)]
+    body: &'a B,
+    #[serde(flatten)]
+    localized: Option<Localized>,
+}
+
+impl<'a, B: ?Sized> WithLocalized<'a, B> {
+    /// Wrap a serialized HTTP error (e.g. the error itself or [`Exposed`](crate::Exposed)) with the localized
+    /// message of the error for the current request.
+    pub fn new<E: HttpError + Serialize + ?Sized>(body: &'a B, error: &E) -> Self {
+        Self {
+            body,
+            localized: localize(error),
+        }
+    }
+
+    /// Localized message.
+    pub fn localized(&self) -> Option<&Localized> {
+        self.localized.as_ref()
+    }
+}
+
+/// Layer negotiating the locale of HTTP errors from the `Accept-Language` header of requests.
+///
+/// The locales are available to [`localize`] while the request is handled.
+#[derive(Clone)]
+pub struct LocalizeLayer {
+    localizer: Arc<Localizer>,
+}
+
+impl LocalizeLayer {
+    /// Create a layer localizing with the localizer.
+    pub fn new(localizer: Arc<Localizer>) -> Self {
+        Self { localizer }
+    }
+}
+
+impl<S> Layer<S> for LocalizeLayer {
+    type Service = Localize<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Localize {
+            inner,
+            localizer: self.localizer.clone(),
+        }
+    }
+}
+
+/// Service negotiating the locale of HTTP errors from the `Accept-Language` header of requests.
+///
+/// See [`LocalizeLayer`].
+#[derive(Clone)]
+pub struct Localize<S> {
+    inner: S,
+    localizer: Arc<Localizer>,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for Localize<S>
+where
+    S: Service<Request<ReqBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let locales = self.localizer.negotiate(
+            request
+                .headers()
+                .get(ACCEPT_LANGUAGE)
+                .and_then(|value| value.to_str().ok()),
+        );
+
+        let future = self.inner.call(request);
+
+        Box::pin(LOCALES.scope((self.localizer.clone(), locales), future))
+    }
+}
@@ -0,0 +1,126 @@
+//! jsonrpsee utilities.
+
+use std::{collections::HashMap, sync::Arc};
+
+use http::StatusCode;
+use jsonrpsee_types::{
+    ErrorObject, ErrorObjectOwned,
+    error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+};
+use serde::Serialize;
+
+use crate::HttpError;
+
+pub use jsonrpsee_types;
+
+/// HTTP error which can be converted into a JSON-RPC error object.
+pub trait JsonRpcError: HttpError {
+    /// JSON-RPC error code, overriding the [`ErrorCodes`] mapping of the HTTP status code.
+    fn jsonrpc_code(&self) -> Option<i32>;
+}
+
+impl<T: JsonRpcError + ?Sized> JsonRpcError for &T {
+    fn jsonrpc_code(&self) -> Option<i32> {
+        (**self).jsonrpc_code()
+    }
+}
+
+impl<T: JsonRpcError> JsonRpcError for Box<T> {
+    fn jsonrpc_code(&self) -> Option<i32> {
+        (**self).jsonrpc_code()
+    }
+}
+
+impl<T: JsonRpcError> JsonRpcError for Arc<T> {
+    fn jsonrpc_code(&self) -> Option<i32> {
+        (**self).jsonrpc_code()
+    }
+}
+
+/// Mapping from HTTP status codes to JSON-RPC error codes.
+///
+/// By default, `400 Bad Request` and `422 Unprocessable Entity` map to `-32602` (invalid params),
+/// `500 Internal Server Error` maps to `-32603` (internal error), other client errors map to their
+/// negated status code (e.g. `-404`) and other server errors map to the implementation-defined
+/// server error range (e.g. `-32003` for `503 Service Unavailable`).
+#[derive(Clone, Debug)]
+pub struct ErrorCodes {
+    codes: HashMap<StatusCode, i32>,
+    client_errors: i32,
+    server_errors: i32,
+}
+
+impl ErrorCodes {
+    /// Create the default mapping.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Map a status code to a JSON-RPC error code.
+    pub fn status(mut self, status: StatusCode, code: i32) -> Self {
+        self.codes.insert(status, code);
+        self
+    }
+
+    /// Set the JSON-RPC error code of `400 Bad Request`, subsequent client errors count down from it.
+    pub fn client_errors(mut self, start: i32) -> Self {
+        self.client_errors = start;
+        self
+    }
+
+    /// Set the JSON-RPC error code of `500 Internal Server Error`, subsequent server errors count down from it.
+    pub fn server_errors(mut self, start: i32) -> Self {
+        self.server_errors = start;
+        self
+    }
+
+    /// JSON-RPC error code for a status code.
+    pub fn code(&self, status: StatusCode) -> i32 {
+        if let Some(code) = self.codes.get(&status) {
+            *code
+        } else if status.is_client_error() {
+            self.client_errors - (i32::from(status.as_u16()) - 400)
+        } else if status.is_server_error() {
+            self.server_errors - (i32::from(status.as_u16()) - 500)
+        } else {
+            INTERNAL_ERROR_CODE
+        }
+    }
+}
+
+impl Default for ErrorCodes {
+    fn default() -> Self {
+        Self {
+            codes: HashMap::from_iter([
+                (StatusCode::BAD_REQUEST, INVALID_PARAMS_CODE),
+                (StatusCode::UNPROCESSABLE_ENTITY, INVALID_PARAMS_CODE),
+                (StatusCode::INTERNAL_SERVER_ERROR, INTERNAL_ERROR_CODE),
+            ]),
+            client_errors: -400,
+            server_errors: -32000,
+        }
+    }
+}
+
+/// Convert an HTTP error into a JSON-RPC error object.
+///
+/// The message is the error code, or the canonical reason of the status code if the error has no code.
+/// The data is the serialized error, according to its [`Exposure`](crate::Exposure).
+pub fn error_object<E: JsonRpcError + Serialize>(
+    error: &E,
+    codes: &ErrorCodes,
+) -> ErrorObjectOwned {
+    let status = error.status();
+
+    // The error code is already the message, the data has no tag.
+    let data = crate::Exposed::new(error, None);
+
+    ErrorObject::owned(
+        error.jsonrpc_code().unwrap_or_else(|| codes.code(status)),
+        error
+            .code()
+            .or_else(|| status.canonical_reason())
+            .unwrap_or_default(),
+        Some(data),
+    )
+}
@@ -0,0 +1,98 @@
+//! Leptos utilities.
+
+use std::{
+    cell::Cell,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use http::{Request, Response, StatusCode};
+use server_fn::{
+    Bytes, Encodes,
+    error::{FromServerFnError, SERVER_FN_ERROR_HEADER, ServerFnErrorErr},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::HttpError;
+
+pub use server_fn;
+
+tokio::task_local! {
+    static STATUS: Cell<Option<StatusCode>>;
+}
+
+/// Serialize a server function error and record its status for [`ServerFnStatusLayer`].
+///
+/// The client deserializes the error type itself, so the error is serialized completely regardless of its
+/// [`Exposure`](crate::Exposure). Skip internal payloads (e.g. `#[serde(skip)]`) of server function errors.
+pub fn ser<E: HttpError + FromServerFnError>(error: &E) -> Bytes {
+    crate::notify(error);
+
+    let masked = error.masked();
+    let error = masked.as_ref().unwrap_or(error);
+
+    // Outside of a `ServerFnStatusLayer` (e.g. on the client) there is no status to record.
+    _ = STATUS.try_with(|status| status.set(Some(error.status())));
+
+    E::Encoder::encode(error).unwrap_or_else(|err| {
+        E::Encoder::encode(&E::from_server_fn_error(ServerFnErrorErr::Serialization(
+            err.to_string(),
+        )))
+        .expect("serialized server function error")
+    })
+}
+
+/// Layer applying the status of HTTP errors to server function error responses.
+///
+/// Server functions always respond with [`StatusCode::INTERNAL_SERVER_ERROR`] when they return an error.
+/// This layer replaces it with the status of the HTTP error, so it is preserved for the client.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ServerFnStatusLayer;
+
+impl<S> Layer<S> for ServerFnStatusLayer {
+    type Service = ServerFnStatus<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ServerFnStatus { inner }
+    }
+}
+
+/// Service applying the status of HTTP errors to server function error responses.
+///
+/// See [`ServerFnStatusLayer`].
+#[derive(Clone, Copy, Debug)]
+pub struct ServerFnStatus<S> {
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for ServerFnStatus<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let future = self.inner.call(request);
+
+        Box::pin(STATUS.scope(Cell::new(None), async move {
+            let mut response = future.await?;
+
+            if response.headers().contains_key(SERVER_FN_ERROR_HEADER)
+                && let Some(status) = STATUS.with(Cell::get)
+            {
+                *response.status_mut() = status;
+            }
+
+            Ok(response)
+        }))
+    }
+}
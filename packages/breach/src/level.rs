@@ -0,0 +1,33 @@
+use http::StatusCode;
+
+/// Severity of an HTTP error.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Level {
+    /// Error level.
+    Error,
+    /// Warn level.
+    Warn,
+    /// Info level.
+    Info,
+    /// Debug level.
+    Debug,
+    /// Trace level.
+    Trace,
+}
+
+impl Level {
+    /// Default severity of a status code.
+    ///
+    /// Server errors are [`Level::Error`]. `401 Unauthorized`, `403 Forbidden` and `429 Too Many Requests` are
+    /// [`Level::Warn`], other client errors are [`Level::Info`]. Any other status code is [`Level::Debug`].
+    pub fn from_status(status: StatusCode) -> Self {
+        match status {
+            status if status.is_server_error() => Level::Error,
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS => {
+                Level::Warn
+            }
+            status if status.is_client_error() => Level::Info,
+            _ => Level::Debug,
+        }
+    }
+}
@@ -2,11 +2,43 @@
 
 //! Breach.
 
+#[doc(hidden)]
+pub mod __private;
+#[cfg(feature = "client")]
+pub mod client;
+#[cfg(feature = "dyn")]
+mod dynamic;
 mod error;
+mod exposure;
+#[cfg(feature = "fluent")]
+pub mod fluent;
+#[cfg(feature = "jsonrpsee")]
+pub mod jsonrpsee;
+#[cfg(feature = "leptos")]
+pub mod leptos;
+mod level;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "negotiate")]
+pub mod negotiate;
+mod notify;
+#[cfg(feature = "opentelemetry")]
+pub mod opentelemetry;
+#[cfg(feature = "request-id")]
+pub mod request_id;
+#[cfg(feature = "sentry")]
+pub mod sentry;
+#[cfg(feature = "tracing")]
+pub mod tracing;
 #[cfg(feature = "utoipa")]
 pub mod utoipa;
 
+#[cfg(feature = "dyn")]
+pub use dynamic::*;
 pub use error::*;
+pub use exposure::*;
+pub use level::*;
+pub use notify::*;
 
 #[cfg(feature = "macros")]
 pub use breach_macros::*;
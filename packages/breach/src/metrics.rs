@@ -0,0 +1,85 @@
+//! Metrics utilities.
+
+use std::{
+    any::type_name,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Instant,
+};
+
+use metrics::{counter, histogram};
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::HttpError;
+
+/// Name of the counter of HTTP error responses.
+pub const ERRORS_TOTAL: &str = "http_errors_total";
+
+/// Name of the histogram of the time from the start of the request to the HTTP error response, in seconds.
+pub const ERROR_DURATION_SECONDS: &str = "http_error_duration_seconds";
+
+tokio::task_local! {
+    static START: Instant;
+}
+
+/// Record an HTTP error response as metrics.
+///
+/// Increments the [`ERRORS_TOTAL`] counter, labelled by `status`, `code` (empty if there is none) and `type`
+/// (the Rust type name). Within a [`RequestStartLayer`], the time since the start of the request is recorded in the
+/// [`ERROR_DURATION_SECONDS`] histogram with the same labels.
+pub fn record<E: HttpError + ?Sized>(error: &E) {
+    let labels = [
+        ("status", error.status().as_str().to_owned()),
+        ("code", error.code().unwrap_or_default().to_owned()),
+        ("type", type_name::<E>().to_owned()),
+    ];
+
+    counter!(ERRORS_TOTAL, &labels).increment(1);
+
+    if let Ok(start) = START.try_with(Instant::elapsed) {
+        histogram!(ERROR_DURATION_SECONDS, &labels).record(start.as_secs_f64());
+    }
+}
+
+/// Layer recording the start of requests for the [`ERROR_DURATION_SECONDS`] histogram.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RequestStartLayer;
+
+impl<S> Layer<S> for RequestStartLayer {
+    type Service = RequestStart<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestStart { inner }
+    }
+}
+
+/// Service recording the start of requests for the [`ERROR_DURATION_SECONDS`] histogram.
+///
+/// See [`RequestStartLayer`].
+#[derive(Clone, Copy, Debug)]
+pub struct RequestStart<S> {
+    inner: S,
+}
+
+impl<S, Request> Service<Request> for RequestStart<S>
+where
+    S: Service<Request>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let start = Instant::now();
+        let future = self.inner.call(request);
+
+        Box::pin(START.scope(start, future))
+    }
+}
@@ -0,0 +1,605 @@
+//! Content negotiation utilities.
+
+use std::{
+    error::Error,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use http::{Request, StatusCode, header::ACCEPT};
+use serde_json::{Map, Value};
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{Environment, Exposure, HttpError, environment, notify::EncodeError};
+
+/// Content types of the default [`Encoders`].
+///
+/// Documented by `#[http(negotiate, utoipa)]`. Errors responding with other encoders should list their content types
+/// with `#[http(negotiate(content_types = ..))]` instead.
+pub const CONTENT_TYPES: &[&str] = &[
+    JSON,
+    PROBLEM_JSON,
+    TEXT,
+    #[cfg(feature = "msgpack")]
+    MSGPACK,
+    #[cfg(feature = "xml")]
+    XML,
+    #[cfg(feature = "html")]
+    HTML,
+];
+
+const JSON: &str = "application/json";
+const PROBLEM_JSON: &str = "application/problem+json";
+const TEXT: &str = "text/plain; charset=utf-8";
+#[cfg(feature = "msgpack")]
+const MSGPACK: &str = "application/msgpack";
+#[cfg(feature = "xml")]
+const XML: &str = "application/xml";
+#[cfg(feature = "html")]
+const HTML: &str = "text/html; charset=utf-8";
+
+#[cfg(feature = "html")]
+const HTML_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{{ status }}{% if reason %} {{ reason }}{% endif %}</title>
+</head>
+<body>
+<h1>{{ status }}{% if reason %} {{ reason }}{% endif %}</h1>
+{% if message %}<p>{{ message }}</p>
+{% endif %}</body>
+</html>
+"#;
+
+#[cfg(feature = "html")]
+pub use minijinja;
+
+tokio::task_local! {
+    static NEGOTIATION: Negotiation;
+}
+
+struct Negotiation {
+    encoders: Arc<Encoders>,
+    accept: Option<String>,
+    path: String,
+}
+
+/// HTTP error to encode as response body.
+pub struct ErrorBody<'a> {
+    status: StatusCode,
+    reason: Option<&'static str>,
+    code: Option<&'static str>,
+    body: &'a dyn erased_serde::Serialize,
+    message: Option<String>,
+}
+
+impl<'a> ErrorBody<'a> {
+    /// Create an HTTP error body from the serialized body (e.g. the error itself or [`Exposed`](crate::Exposed))
+    /// and the message of the error.
+    ///
+    /// The message of errors with [`Exposure::Internal`] is omitted in production.
+    pub fn new<E: HttpError + ?Sized>(
+        error: &E,
+        body: &'a dyn erased_serde::Serialize,
+        message: Option<String>,
+    ) -> Self {
+        let exposed =
+            environment() == Environment::Development || error.exposure() == Exposure::Public;
+
+        Self {
+            status: error.status(),
+            reason: error.reason(),
+            code: error.code(),
+            body,
+            message: message.filter(|_| exposed),
+        }
+    }
+
+    /// HTTP status code.
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Reason phrase of the HTTP status code.
+    pub fn reason(&self) -> Option<&'static str> {
+        self.reason
+    }
+
+    /// Error code.
+    pub fn code(&self) -> Option<&'static str> {
+        self.code
+    }
+
+    /// Serialized body.
+    pub fn body(&self) -> &'a dyn erased_serde::Serialize {
+        self.body
+    }
+
+    /// Message of the HTTP error, if it implements [`Display`](std::fmt::Display).
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+}
+
+/// Encoder of HTTP error bodies for a content type.
+pub trait ErrorEncoder: Send + Sync {
+    /// Content type of the encoded body, e.g. `application/json`.
+    fn content_type(&self) -> &'static str;
+
+    /// Encode the HTTP error.
+    ///
+    /// The path of the current request is available as `instance`, within a [`NegotiateLayer`].
+    fn encode(
+        &self,
+        error: &ErrorBody<'_>,
+        instance: Option<&str>,
+    ) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>>;
+}
+
+/// Encoder of `application/json` bodies.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JsonEncoder;
+
+impl ErrorEncoder for JsonEncoder {
+    fn content_type(&self) -> &'static str {
+        JSON
+    }
+
+    fn encode(
+        &self,
+        error: &ErrorBody<'_>,
+        _instance: Option<&str>,
+    ) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        Ok(serde_json::to_vec(error.body)?)
+    }
+}
+
+/// Encoder of `application/problem+json` bodies (RFC 9457).
+///
+/// The members of the body are extension members of the problem details.
+#[derive(Clone, Debug, Default)]
+pub struct ProblemJsonEncoder {
+    type_base: Option<String>,
+    instance: bool,
+}
+
+impl ProblemJsonEncoder {
+    /// Create an encoder with `about:blank` as problem type and without `instance`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the problem type to the error code appended to `type_base`, e.g. `https://example.com/problems/`.
+    pub fn with_type_base(mut self, type_base: impl Into<String>) -> Self {
+        self.type_base = Some(type_base.into());
+        self
+    }
+
+    /// Set `instance` to the path of the request.
+    pub fn with_instance(mut self, instance: bool) -> Self {
+        self.instance = instance;
+        self
+    }
+}
+
+// Members of problem details, which take precedence over members of the body with the same name.
+const PROBLEM_MEMBERS: [&str; 5] = ["type", "title", "status", "detail", "instance"];
+
+impl ErrorEncoder for ProblemJsonEncoder {
+    fn content_type(&self) -> &'static str {
+        PROBLEM_JSON
+    }
+
+    fn encode(
+        &self,
+        error: &ErrorBody<'_>,
+        instance: Option<&str>,
+    ) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        let r#type = match (&self.type_base, error.code) {
+            (Some(type_base), Some(code)) => format!("{type_base}{code}"),
+            _ => "about:blank".to_owned(),
+        };
+
+        let mut problem = Map::new();
+        problem.insert("type".to_owned(), r#type.into());
+        if let Some(title) = error.reason {
+            problem.insert("title".to_owned(), title.into());
+        }
+        problem.insert("status".to_owned(), error.status.as_u16().into());
+        if let Some(detail) = error.message() {
+            problem.insert("detail".to_owned(), detail.into());
+        }
+        if let Some(instance) = instance.filter(|_| self.instance) {
+            problem.insert("instance".to_owned(), instance.into());
+        }
+
+        if let Value::Object(body) = serde_json::to_value(error.body)? {
+            problem.extend(
+                body.into_iter()
+                    .filter(|(name, _)| !PROBLEM_MEMBERS.contains(&name.as_str())),
+            );
+        }
+
+        Ok(serde_json::to_vec(&problem)?)
+    }
+}
+
+/// Encoder of `text/plain` bodies, the message of the HTTP error or the reason phrase of its status code.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TextEncoder;
+
+impl ErrorEncoder for TextEncoder {
+    fn content_type(&self) -> &'static str {
+        TEXT
+    }
+
+    fn encode(
+        &self,
+        error: &ErrorBody<'_>,
+        _instance: Option<&str>,
+    ) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        let status = match error.reason {
+            Some(reason) => format!("{} {reason}", error.status.as_str()),
+            None => error.status.as_str().to_owned(),
+        };
+        let text = match (error.message(), error.code) {
+            (Some(message), _) => message.to_owned(),
+            (None, Some(code)) => format!("{status} ({code})"),
+            (None, None) => status,
+        };
+
+        Ok(text.into_bytes())
+    }
+}
+
+/// Encoder of `application/msgpack` bodies.
+#[cfg(feature = "msgpack")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MsgpackEncoder;
+
+#[cfg(feature = "msgpack")]
+impl ErrorEncoder for MsgpackEncoder {
+    fn content_type(&self) -> &'static str {
+        MSGPACK
+    }
+
+    fn encode(
+        &self,
+        error: &ErrorBody<'_>,
+        _instance: Option<&str>,
+    ) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        Ok(rmp_serde::to_vec_named(error.body)?)
+    }
+}
+
+/// Encoder of `application/xml` bodies, with `error` as root element.
+#[cfg(feature = "xml")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct XmlEncoder;
+
+#[cfg(feature = "xml")]
+impl ErrorEncoder for XmlEncoder {
+    fn content_type(&self) -> &'static str {
+        XML
+    }
+
+    fn encode(
+        &self,
+        error: &ErrorBody<'_>,
+        _instance: Option<&str>,
+    ) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        let mut xml = String::new();
+        serde::Serialize::serialize(
+            error.body,
+            quick_xml::se::Serializer::with_root(&mut xml, Some("error"))?,
+        )?;
+
+        Ok(xml.into_bytes())
+    }
+}
+
+/// Encoder of `text/html` bodies, rendered with [MiniJinja](minijinja) templates.
+///
+/// Templates receive `status`, `reason`, `code`, `message` (public messages only in production), `instance` and the
+/// serialized `error`. The default template shows the status, reason and message.
+#[cfg(feature = "html")]
+pub struct HtmlEncoder {
+    environment: minijinja::Environment<'static>,
+}
+
+#[cfg(feature = "html")]
+impl HtmlEncoder {
+    const DEFAULT: &'static str = "error.html";
+
+    /// Create an encoder with the default template.
+    pub fn new() -> Self {
+        let mut environment = minijinja::Environment::new();
+        environment
+            .add_template(Self::DEFAULT, HTML_TEMPLATE)
+            .expect("default template should be valid");
+
+        Self { environment }
+    }
+
+    /// Replace the default template, used for errors without a template for their code.
+    pub fn with_default_template(
+        self,
+        source: impl Into<String>,
+    ) -> Result<Self, minijinja::Error> {
+        self.with_named_template(Self::DEFAULT.to_owned(), source.into())
+    }
+
+    /// Add a template for errors with a code (e.g. `notFound`), to override the page of a variant.
+    pub fn with_template(
+        self,
+        code: &str,
+        source: impl Into<String>,
+    ) -> Result<Self, minijinja::Error> {
+        self.with_named_template(format!("{code}.html"), source.into())
+    }
+
+    fn with_named_template(
+        mut self,
+        name: String,
+        source: String,
+    ) -> Result<Self, minijinja::Error> {
+        // Names ending with `.html` enable HTML escaping.
+        self.environment.add_template_owned(name, source)?;
+
+        Ok(self)
+    }
+}
+
+#[cfg(feature = "html")]
+impl Default for HtmlEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "html")]
+impl ErrorEncoder for HtmlEncoder {
+    fn content_type(&self) -> &'static str {
+        HTML
+    }
+
+    fn encode(
+        &self,
+        error: &ErrorBody<'_>,
+        instance: Option<&str>,
+    ) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        let template = error
+            .code
+            .and_then(|code| self.environment.get_template(&format!("{code}.html")).ok())
+            .map_or_else(|| self.environment.get_template(Self::DEFAULT), Ok)?;
+
+        let html = template.render(minijinja::context! {
+            status => error.status.as_u16(),
+            reason => error.reason,
+            code => error.code,
+            message => error.message(),
+            instance => instance,
+            error => minijinja::Value::from_serialize(error.body),
+        })?;
+
+        Ok(html.into_bytes())
+    }
+}
+
+/// Encoders to choose from by the `Accept` header of requests. The first encoder is the fallback.
+pub struct Encoders {
+    encoders: Vec<Box<dyn ErrorEncoder>>,
+}
+
+impl Encoders {
+    /// Create encoders with [`JsonEncoder`] as fallback.
+    pub fn new() -> Self {
+        Self {
+            encoders: vec![Box::new(JsonEncoder)],
+        }
+    }
+
+    /// Add an encoder, replacing the encoder of the same content type.
+    pub fn with(mut self, encoder: impl ErrorEncoder + 'static) -> Self {
+        match self
+            .encoders
+            .iter()
+            .position(|existing| existing.content_type() == encoder.content_type())
+        {
+            Some(index) => self.encoders[index] = Box::new(encoder),
+            None => self.encoders.push(Box::new(encoder)),
+        }
+        self
+    }
+
+    /// Content types of the encoders.
+    pub fn content_types(&self) -> Vec<&'static str> {
+        self.encoders
+            .iter()
+            .map(|encoder| encoder.content_type())
+            .collect()
+    }
+
+    /// Encoder for an `Accept` header, by quality and order of the media ranges.
+    pub fn negotiate(&self, accept: Option<&str>) -> &dyn ErrorEncoder {
+        let mut ranges = accept
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|range| {
+                let mut parameters = range.split(';').map(str::trim);
+                let media_range = parameters.next().filter(|range| !range.is_empty())?;
+                let quality = parameters
+                    .find_map(|parameter| parameter.strip_prefix("q="))
+                    .and_then(|quality| quality.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+
+                Some((media_range.to_ascii_lowercase(), quality))
+            })
+            .filter(|(_, quality)| *quality > 0.0)
+            .collect::<Vec<_>>();
+        ranges.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+        ranges
+            .iter()
+            .find_map(|(range, _)| {
+                self.encoders
+                    .iter()
+                    .find(|encoder| matches(range, encoder.content_type()))
+            })
+            .or(self.encoders.first())
+            .map_or(&JsonEncoder, |encoder| encoder.as_ref())
+    }
+
+    /// Encode an HTTP error for an `Accept` header, returning the content type and body.
+    ///
+    /// Falls back to JSON if the encoder fails, and to an empty JSON object if that fails as well.
+    /// Failures are [notified](crate::notify) as internal server errors.
+    pub fn encode(
+        &self,
+        error: &ErrorBody<'_>,
+        accept: Option<&str>,
+        instance: Option<&str>,
+    ) -> (&'static str, Vec<u8>) {
+        let encoder = self.negotiate(accept);
+
+        let err = match encoder.encode(error, instance) {
+            Ok(body) => return (encoder.content_type(), body),
+            Err(err) => err,
+        };
+        crate::notify(&EncodeError(err));
+
+        match JsonEncoder.encode(error, instance) {
+            Ok(body) => (JSON, body),
+            Err(err) => {
+                crate::notify(&EncodeError(err));
+
+                (JSON, b"{}".to_vec())
+            }
+        }
+    }
+}
+
+/// Default encoders, for the [`CONTENT_TYPES`].
+impl Default for Encoders {
+    fn default() -> Self {
+        let encoders = Self::new()
+            .with(ProblemJsonEncoder::new())
+            .with(TextEncoder);
+        #[cfg(feature = "msgpack")]
+        let encoders = encoders.with(MsgpackEncoder);
+        #[cfg(feature = "xml")]
+        let encoders = encoders.with(XmlEncoder);
+        #[cfg(feature = "html")]
+        let encoders = encoders.with(HtmlEncoder::new());
+
+        encoders
+    }
+}
+
+fn matches(range: &str, content_type: &str) -> bool {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    match range.split_once('/') {
+        Some(("*", "*")) => true,
+        Some((r#type, "*")) => essence.split('/').next() == Some(r#type),
+        _ => essence == range,
+    }
+}
+
+/// Encode an HTTP error for the current request, within a [`NegotiateLayer`], returning the content type and body.
+///
+/// Outside of a [`NegotiateLayer`], HTTP errors are encoded as JSON. Derived with `#[http(negotiate)]`.
+pub fn encode(error: &ErrorBody<'_>) -> (&'static str, Vec<u8>) {
+    NEGOTIATION
+        .try_with(|negotiation| {
+            negotiation.encoders.encode(
+                error,
+                negotiation.accept.as_deref(),
+                Some(&negotiation.path),
+            )
+        })
+        .unwrap_or_else(|_| Encoders::new().encode(error, None, None))
+}
+
+/// Layer choosing the encoder of HTTP errors by the `Accept` header of requests.
+///
+/// The encoders are available to [`encode`] while the request is handled.
+#[derive(Clone)]
+pub struct NegotiateLayer {
+    encoders: Arc<Encoders>,
+}
+
+impl NegotiateLayer {
+    /// Create a layer choosing from the encoders.
+    pub fn new(encoders: Encoders) -> Self {
+        Self {
+            encoders: Arc::new(encoders),
+        }
+    }
+}
+
+/// Layer choosing from the default [`Encoders`].
+impl Default for NegotiateLayer {
+    fn default() -> Self {
+        Self::new(Encoders::default())
+    }
+}
+
+impl<S> Layer<S> for NegotiateLayer {
+    type Service = Negotiate<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Negotiate {
+            inner,
+            encoders: self.encoders.clone(),
+        }
+    }
+}
+
+/// Service choosing the encoder of HTTP errors by the `Accept` header of requests.
+///
+/// See [`NegotiateLayer`].
+#[derive(Clone)]
+pub struct Negotiate<S> {
+    inner: S,
+    encoders: Arc<Encoders>,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for Negotiate<S>
+where
+    S: Service<Request<ReqBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let negotiation = Negotiation {
+            encoders: self.encoders.clone(),
+            accept: request
+                .headers()
+                .get(ACCEPT)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned),
+            path: request.uri().path().to_owned(),
+        };
+
+        let future = self.inner.call(request);
+
+        Box::pin(NEGOTIATION.scope(negotiation, future))
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(any(feature = "dyn", feature = "negotiate"))]
+use std::error::Error;
+
+#[cfg(any(feature = "dyn", feature = "negotiate"))]
+use http::StatusCode;
+
+use crate::HttpError;
+
+/// Notify that an HTTP error is used as response.
+///
+/// Calls [`HttpError::hook`] and records the error with the enabled integrations
+/// (`metrics`, `opentelemetry`, `sentry` and `tracing`).
+/// The derived integrations call this, so it only needs to be called for manually built responses.
+pub fn notify<E: HttpError + ?Sized>(error: &E) {
+    error.hook();
+
+    #[cfg(feature = "metrics")]
+    crate::metrics::record(error);
+
+    #[cfg(feature = "opentelemetry")]
+    crate::opentelemetry::record(error);
+
+    #[cfg(feature = "sentry")]
+    crate::sentry::record(error);
+
+    #[cfg(feature = "tracing")]
+    crate::tracing::record(error);
+}
+
+/// Failure to encode the body of an HTTP error response, notified in addition to the HTTP error.
+#[cfg(any(feature = "dyn", feature = "negotiate"))]
+pub(crate) struct EncodeError(pub Box<dyn Error + Send + Sync>);
+
+#[cfg(any(feature = "dyn", feature = "negotiate"))]
+impl HttpError for EncodeError {
+    fn status(&self) -> StatusCode {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+
+    fn exposure(&self) -> crate::Exposure {
+        crate::Exposure::Internal
+    }
+
+    fn internal(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.0)
+    }
+
+    fn path(&self) -> Vec<&'static str> {
+        vec!["EncodeError"]
+    }
+
+    fn hook(&self) {}
+}
@@ -0,0 +1,53 @@
+//! OpenTelemetry utilities.
+
+use std::{any::type_name, error::Error, iter::successors};
+
+use opentelemetry::{
+    Context, KeyValue,
+    trace::{Status, TraceContextExt},
+};
+
+use crate::{HttpError, Level};
+
+/// Record an HTTP error response on the active span.
+///
+/// The span status is set to error for server errors and errors with [`Level::Error`] (e.g. client errors with
+/// `#[http(level = error)]`). The attributes `http.response.status_code` and `error.type` (the error code, or the
+/// Rust type name if there is none) are set. The [`HttpError::internal`] error is recorded as `exception` event,
+/// with the messages of its sources appended to the message.
+pub fn record<E: HttpError + ?Sized>(error: &E) {
+    let context = Context::current();
+    let span = context.span();
+    if !span.is_recording() {
+        return;
+    }
+
+    let status = error.status();
+    let r#type = error.code().unwrap_or_else(|| type_name::<E>());
+
+    span.set_attributes([
+        KeyValue::new("http.response.status_code", i64::from(status.as_u16())),
+        KeyValue::new("error.type", r#type),
+    ]);
+
+    if status.is_server_error() || error.level() == Level::Error {
+        span.set_status(Status::error(r#type));
+    }
+
+    if let Some(internal) = error.internal() {
+        span.add_event(
+            "exception",
+            vec![
+                KeyValue::new("exception.type", type_name::<E>()),
+                KeyValue::new("exception.message", message(internal)),
+            ],
+        );
+    }
+}
+
+fn message(error: &(dyn Error + 'static)) -> String {
+    successors(Some(error), |&error| error.source())
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(": ")
+}
@@ -0,0 +1,127 @@
+//! Request ID utilities.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use http::{HeaderName, HeaderValue, Request, Response};
+use serde::Serialize;
+use tower_layer::Layer;
+use tower_service::Service;
+use uuid::Uuid;
+
+/// Header containing the request ID.
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Request ID of the current request, within a [`RequestIdLayer`].
+pub fn current() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// HTTP error with the request ID of the current request.
+///
+/// Serializes as the error with an additional `requestId` member. Derived with `#[http(request_id)]`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithRequestId<'a, E> {
+    #[serde(flatten)]
+    error: &'a E,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+}
+
+impl<'a, E> WithRequestId<'a, E> {
+    /// Wrap an HTTP error with the request ID of the current request.
+    pub fn new(error: &'a E) -> Self {
+        Self {
+            error,
+            request_id: current(),
+        }
+    }
+
+    /// Request ID.
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+}
+
+/// Layer assigning or propagating the request ID of requests.
+///
+/// The request ID is taken from the [`REQUEST_ID_HEADER`] of the request, or a random UUID if it is missing.
+/// It is available with [`current`] while the request is handled and added to the response headers.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RequestIdLayer;
+
+impl<S> Layer<S> for RequestIdLayer {
+    type Service = RequestId<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestId { inner }
+    }
+}
+
+/// Service assigning or propagating the request ID of requests.
+///
+/// See [`RequestIdLayer`].
+#[derive(Clone, Copy, Debug)]
+pub struct RequestId<S> {
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for RequestId<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        let request_id = match request
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .filter(|value| !value.is_empty())
+        {
+            Some(request_id) => request_id.to_owned(),
+            None => {
+                let request_id = Uuid::new_v4().to_string();
+
+                request.headers_mut().insert(
+                    REQUEST_ID_HEADER,
+                    HeaderValue::from_str(&request_id)
+                        .expect("UUID should be a valid header value"),
+                );
+
+                request_id
+            }
+        };
+
+        let header = HeaderValue::from_str(&request_id).ok();
+        let future = self.inner.call(request);
+
+        Box::pin(REQUEST_ID.scope(request_id, async move {
+            let mut response = future.await?;
+
+            if let Some(header) = header {
+                response
+                    .headers_mut()
+                    .entry(REQUEST_ID_HEADER)
+                    .or_insert(header);
+            }
+
+            Ok(response)
+        }))
+    }
+}
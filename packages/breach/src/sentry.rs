@@ -0,0 +1,63 @@
+//! Sentry utilities.
+
+use std::{any::type_name, borrow::Cow};
+
+use sentry_core::{
+    capture_event, event_from_error,
+    protocol::{Event, Level as SentryLevel},
+};
+
+use crate::{HttpError, Level};
+
+impl From<Level> for SentryLevel {
+    fn from(value: Level) -> Self {
+        match value {
+            Level::Error => SentryLevel::Error,
+            Level::Warn => SentryLevel::Warning,
+            Level::Info => SentryLevel::Info,
+            Level::Debug | Level::Trace => SentryLevel::Debug,
+        }
+    }
+}
+
+/// Capture an HTTP error response as Sentry event, if it is [reported](HttpError::is_reported).
+///
+/// The exceptions of the event are the [`HttpError::internal`] error and its sources. Errors without an internal
+/// error are captured as message. The event is tagged with `http.status_code` and `error.code` and uses the
+/// [`HttpError::path`] as fingerprint, so the variants of an error are grouped separately.
+pub fn record<E: HttpError + ?Sized>(error: &E) {
+    if !error.is_reported() {
+        return;
+    }
+
+    let status = error.status();
+    let code = error.code();
+
+    let mut event = match error.internal() {
+        Some(internal) => event_from_error(internal),
+        None => Event {
+            message: code
+                .or_else(|| status.canonical_reason())
+                .map(ToOwned::to_owned),
+            ..Default::default()
+        },
+    };
+
+    event.level = error.level().into();
+    event
+        .tags
+        .insert("http.status_code".to_owned(), status.as_str().to_owned());
+    if let Some(code) = code {
+        event.tags.insert("error.code".to_owned(), code.to_owned());
+    }
+
+    let path = error.path();
+    event.fingerprint = if path.is_empty() {
+        vec![Cow::Borrowed(type_name::<E>())]
+    } else {
+        path.into_iter().map(Cow::Borrowed).collect()
+    }
+    .into();
+
+    capture_event(event);
+}
@@ -0,0 +1,61 @@
+//! tracing utilities.
+
+use std::any::type_name;
+
+use tracing::{Span, event};
+
+use crate::{HttpError, Level};
+
+impl From<Level> for tracing::Level {
+    fn from(value: Level) -> Self {
+        match value {
+            Level::Error => tracing::Level::ERROR,
+            Level::Warn => tracing::Level::WARN,
+            Level::Info => tracing::Level::INFO,
+            Level::Debug => tracing::Level::DEBUG,
+            Level::Trace => tracing::Level::TRACE,
+        }
+    }
+}
+
+/// Record an HTTP error response as tracing event.
+///
+/// The event has the level of [`HttpError::level`] and the fields `http.response.status_code`, `error.code`,
+/// `error.type` (the Rust type name) and `error` (the [`HttpError::internal`] error, including its sources).
+///
+/// The same fields, except for `error`, are recorded on the current span, if it declares them
+/// (e.g. `http.response.status_code = tracing::field::Empty`).
+pub fn record<E: HttpError + ?Sized>(error: &E) {
+    let status = error.status().as_u16();
+    let code = error.code();
+    let r#type = type_name::<E>();
+    let internal = error.internal();
+
+    let span = Span::current();
+    span.record("http.response.status_code", status);
+    if let Some(code) = code {
+        span.record("error.code", code);
+    }
+    span.record("error.type", r#type);
+
+    // Events require a constant level for their static callsite, so there is a callsite per level.
+    let level = tracing::Level::from(error.level());
+    macro_rules! record {
+        ($($level:ident),*) => {
+            $(
+                if level == tracing::Level::$level {
+                    event!(
+                        tracing::Level::$level,
+                        "http.response.status_code" = status,
+                        "error.code" = code,
+                        "error.type" = r#type,
+                        error = internal,
+                        "HTTP error response"
+                    );
+                }
+            )*
+        };
+    }
+
+    record!(ERROR, WARN, INFO, DEBUG, TRACE);
+}
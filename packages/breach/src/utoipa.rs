@@ -5,7 +5,8 @@ use std::collections::BTreeMap;
 use http::StatusCode;
 use itertools::Itertools;
 use utoipa::openapi::{
-    Content, ContentBuilder, OneOfBuilder, RefOr, Response, ResponseBuilder, Schema,
+    AllOfBuilder, Content, ContentBuilder, ObjectBuilder, OneOfBuilder, RefOr, Response,
+    ResponseBuilder, Schema, Type,
 };
 
 /// Merge multiple [`BTreeMap<String, RefOr<Response>>`] into a single [`BTreeMap<String, RefOr<Response>>`].
@@ -14,35 +15,197 @@ pub fn merge_responses(
 ) -> BTreeMap<String, RefOr<Response>> {
     responses
         .flatten()
+        .sorted_by(|(a, _), (b, _)| a.cmp(b))
         .chunk_by(|(code, _)| code.clone())
         .into_iter()
         .map(|(code, chunk)| {
-            let response = merge_response(
-                StatusCode::from_bytes(code.as_bytes()).expect("valid status code"),
-                chunk.map(|(_, response)| response),
-            );
+            let response = merge_response(&code, chunk.map(|(_, response)| response));
 
             (code, RefOr::T(response))
         })
         .collect()
 }
 
+/// Move the responses of [`BTreeMap<String, RefOr<Response>>`] to other status codes, as with `#[http(remap(..))]`.
+///
+/// Responses moved to a status code which already has a response are merged. Moved responses are described by the
+/// reason of the new status code, unless other responses with that status code have a description.
+pub fn remap_responses(
+    responses: BTreeMap<String, RefOr<Response>>,
+    remap: &[(StatusCode, StatusCode)],
+) -> BTreeMap<String, RefOr<Response>> {
+    merge_responses(responses.into_iter().map(|(code, mut response)| {
+        let code = match remap.iter().find(|(from, _)| from.as_str() == code) {
+            Some((_, to)) => {
+                // The description of the original status code doesn't apply, the merge falls back to the new one.
+                if let RefOr::T(response) = &mut response {
+                    response.description.clear();
+                }
+
+                to.as_str().to_owned()
+            }
+            None => code,
+        };
+
+        BTreeMap::from_iter([(code, response)])
+    }))
+}
+
+/// Add the `requestId` member of `#[http(request_id)]` to the schemas of [`BTreeMap<String, RefOr<Response>>`].
+pub fn with_request_id(
+    mut responses: BTreeMap<String, RefOr<Response>>,
+) -> BTreeMap<String, RefOr<Response>> {
+    let request_id = Schema::from(
+        ObjectBuilder::new()
+            .property(
+                "requestId",
+                ObjectBuilder::new()
+                    .schema_type(Type::String)
+                    .description(Some("ID of the request.")),
+            )
+            .build(),
+    );
+
+    for response in responses.values_mut() {
+        let RefOr::T(response) = response else {
+            continue;
+        };
+
+        for content in response.content.values_mut() {
+            let Some(schema) = content.schema.take() else {
+                continue;
+            };
+
+            // Nested errors with `#[http(request_id)]` already have the member.
+            let has_request_id = matches!(
+                &schema,
+                RefOr::T(Schema::AllOf(all_of))
+                    if all_of.items.iter().any(|item| matches!(item, RefOr::T(item) if *item == request_id))
+            );
+
+            content.schema = Some(if has_request_id {
+                schema
+            } else {
+                RefOr::T(Schema::from(
+                    AllOfBuilder::new()
+                        .item(schema)
+                        .item(request_id.clone())
+                        .build(),
+                ))
+            });
+        }
+    }
+
+    responses
+}
+
+/// Add the content types of `#[http(negotiate)]` (e.g. [`CONTENT_TYPES`](crate::negotiate::CONTENT_TYPES)) to the
+/// `application/json` responses of [`BTreeMap<String, RefOr<Response>>`].
+///
+/// Text content types have a string schema and `application/problem+json` has the members of problem details.
+pub fn with_content_types(
+    mut responses: BTreeMap<String, RefOr<Response>>,
+    content_types: &[&str],
+) -> BTreeMap<String, RefOr<Response>> {
+    for response in responses.values_mut() {
+        let RefOr::T(response) = response else {
+            continue;
+        };
+        let Some(json) = response.content.get("application/json").cloned() else {
+            continue;
+        };
+
+        for content_type in content_types {
+            if response.content.contains_key(*content_type) {
+                continue;
+            }
+
+            let content = if content_type.starts_with("text/") {
+                ContentBuilder::new()
+                    .schema(Some(ObjectBuilder::new().schema_type(Type::String)))
+                    .build()
+            } else if content_type.starts_with("application/problem+json") {
+                let mut content = json.clone();
+                let mut all_of = AllOfBuilder::new().item(problem());
+                if let Some(schema) = content.schema.take() {
+                    all_of = all_of.item(schema);
+                }
+                content.schema = Some(RefOr::T(Schema::from(all_of.build())));
+                content
+            } else {
+                json.clone()
+            };
+
+            response.content.insert((*content_type).to_owned(), content);
+        }
+    }
+
+    responses
+}
+
+fn problem() -> Schema {
+    let string = |description: &str| {
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .description(Some(description))
+    };
+
+    Schema::from(
+        ObjectBuilder::new()
+            .property("type", string("URI reference of the problem type."))
+            .required("type")
+            .property("title", string("Summary of the problem type."))
+            .property(
+                "status",
+                ObjectBuilder::new()
+                    .schema_type(Type::Integer)
+                    .description(Some("HTTP status code.")),
+            )
+            .required("status")
+            .property(
+                "detail",
+                string("Explanation of the occurrence of the problem."),
+            )
+            .property(
+                "instance",
+                string("URI reference of the occurrence of the problem."),
+            )
+            .build(),
+    )
+}
+
 /// Merge multiple [`RefOr<Response>`] into a single [`Response`].
-fn merge_response(code: StatusCode, responses: impl Iterator<Item = RefOr<Response>>) -> Response {
-    let responses = responses.filter_map(|response| match response {
-        RefOr::Ref(_) => None,
-        RefOr::T(response) => Some(response),
-    });
+fn merge_response(code: &str, responses: impl Iterator<Item = RefOr<Response>>) -> Response {
+    let responses = responses
+        .filter_map(|response| match response {
+            RefOr::Ref(_) => None,
+            RefOr::T(response) => Some(response),
+        })
+        .collect::<Vec<_>>();
 
     let mut builder = ResponseBuilder::new();
 
-    if let Some(canonical_reason) = code.canonical_reason() {
-        builder = builder.description(canonical_reason)
-    }
+    // Prefer custom reason phrases (e.g. `#[http(status = 499, reason = "..")]`) over the canonical reason.
+    // OpenAPI requires a description, so non-standard status codes fall back to e.g. `Status 520`.
+    let description = responses
+        .iter()
+        .map(|response| response.description.as_str())
+        .find(|description| !description.is_empty())
+        .map(str::to_owned)
+        .unwrap_or_else(|| match StatusCode::from_bytes(code.as_bytes()) {
+            Ok(status) => status
+                .canonical_reason()
+                .map(str::to_owned)
+                .unwrap_or_else(|| format!("Status {code}")),
+            // The code is `default` for responses with a status determined at runtime.
+            Err(_) => "Error with a status determined at runtime".to_owned(),
+        });
+    builder = builder.description(description);
 
     builder = responses
         .into_iter()
         .flat_map(|response| response.content)
+        .sorted_by(|(a, _), (b, _)| a.cmp(b))
         .chunk_by(|(content_type, _)| content_type.clone())
         .into_iter()
         .fold(builder, |builder, (content_type, chunk)| {
@@ -0,0 +1,22 @@
+use breach::HttpError;
+use serde::Serialize;
+
+mod consts {
+    use breach::http::StatusCode;
+
+    pub const UPSTREAM_TIMEOUT: StatusCode = StatusCode::GATEWAY_TIMEOUT;
+}
+
+#[derive(Serialize)]
+struct TimeoutPayload {
+    host: String,
+}
+
+#[derive(HttpError, Serialize)]
+#[serde(tag = "code")]
+enum ProxyError {
+    #[http(status = consts::UPSTREAM_TIMEOUT)]
+    Timeout(TimeoutPayload),
+}
+
+fn main() {}
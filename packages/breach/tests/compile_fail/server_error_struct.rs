@@ -0,0 +1,10 @@
+use breach::HttpError;
+use serde::Serialize;
+
+#[derive(HttpError, Serialize)]
+#[http(status = SERVICE_UNAVAILABLE)]
+struct UnavailableError {
+    host: String,
+}
+
+fn main() {}
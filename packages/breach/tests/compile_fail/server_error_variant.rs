@@ -0,0 +1,16 @@
+use breach::HttpError;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct DatabasePayload {
+    query: String,
+}
+
+#[derive(HttpError, Serialize)]
+#[serde(tag = "code")]
+enum GetUserByIdError {
+    #[http(status = INTERNAL_SERVER_ERROR)]
+    Database(DatabasePayload),
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use breach::HttpError;
+use serde::Serialize;
+
+#[derive(HttpError, Serialize)]
+#[http(status = NOT_FUOND)]
+struct NotFoundError;
+
+fn main() {}